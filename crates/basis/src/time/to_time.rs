@@ -1,4 +1,4 @@
-use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
 
 pub trait ToTime {
     fn to_time(&self) -> DateTime<FixedOffset>;
@@ -7,8 +7,7 @@ pub trait ToTime {
 impl ToTime for i64 {
     fn to_time(&self) -> DateTime<FixedOffset> {
         let china_timezone = FixedOffset::east_opt(8 * 3600).unwrap();
-        let utc_time =
-            DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp_opt(*self, 0).unwrap(), Utc);
+        let utc_time = DateTime::<Utc>::from_timestamp(*self, 0).unwrap();
         utc_time.with_timezone(&china_timezone)
     }
 }
@@ -0,0 +1,116 @@
+// Embed the original source of every exercise listed in info.toml, and of
+// every Exercism crate, so that `rustlings reset` can restore them.
+// The originals are taken from the pristine directory, which mirrors the
+// paths of info.toml, without their `..`. Neither the working tree, where
+// the exercises get solved, nor git, which isn't there when installing
+// from a package, can be trusted to have them.
+// Every file of a Cargo project exercise is embedded separately.
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const PRISTINE_DIR: &str = "pristine";
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_dir = Path::new(&manifest_dir);
+    let info = fs::read_to_string(manifest_dir.join("info.toml")).unwrap();
+    let info: toml::Table = toml::from_str(&info).unwrap();
+    println!("cargo:rerun-if-changed=info.toml");
+    println!("cargo:rerun-if-changed={PRISTINE_DIR}");
+
+    let mut pristine = String::from("pub static PRISTINE_EXERCISES: &[(&str, &str)] = &[\n");
+    // Exercises are listed in topics, or on their own
+    let topics = info.get("topics").and_then(|t| t.as_array());
+    let exercises = topics
+        .into_iter()
+        .flatten()
+        .flat_map(|topic| topic["exercises"].as_array().unwrap())
+        .chain(
+            info.get("exercises")
+                .and_then(|e| e.as_array())
+                .into_iter()
+                .flatten(),
+        );
+    let mut paths: Vec<PathBuf> = exercises
+        .map(|exercise| PathBuf::from(exercise["path"].as_str().unwrap()))
+        .collect();
+    // Every crate in the Exercism directory is an exercise too. Those that
+    // are there when building have to have an original
+    if let Some(exercism) = info.get("exercism").and_then(|e| e.as_str()) {
+        let exercism = Path::new(exercism);
+        let mut crates: Vec<PathBuf> = [
+            manifest_dir.join(exercism),
+            snapshot(manifest_dir, exercism),
+        ]
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("Cargo.toml").is_file())
+        .map(|entry| exercism.join(entry.file_name()))
+        .collect();
+        crates.sort();
+        crates.dedup();
+        paths.extend(crates);
+    }
+    for path in paths {
+        let original = snapshot(manifest_dir, &path);
+        if !original.exists() {
+            panic!(
+                "{} has no original: it should be at {}",
+                path.display(),
+                original.display()
+            );
+        }
+        for file in files(&original) {
+            // What cargo generates in Cargo projects is skipped
+            if file.file_name().is_some_and(|name| name == "Cargo.lock") {
+                continue;
+            }
+            let source = fs::read_to_string(&file).unwrap();
+            let relative = file.strip_prefix(&original).unwrap();
+            let path = if relative.as_os_str().is_empty() {
+                path.clone()
+            } else {
+                path.join(relative)
+            };
+            writeln!(
+                pristine,
+                "    ({:?}, {:?}),",
+                path.to_str().unwrap(),
+                source
+            )
+            .unwrap();
+        }
+    }
+    pristine.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("pristine.rs"), pristine).unwrap();
+}
+
+// Where the original of the file or directory at the given path is
+fn snapshot(manifest_dir: &Path, path: &Path) -> PathBuf {
+    let mut original = manifest_dir.join(PRISTINE_DIR);
+    for component in path.components() {
+        if let Component::Normal(name) = component {
+            original.push(name);
+        }
+    }
+    original
+}
+
+// The files at the given path, in a stable order. A file is listed by itself
+fn files(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    entries.iter().flat_map(|entry| files(entry)).collect()
+}
@@ -0,0 +1,8 @@
+// functions1.rs
+// Make me compile! Execute `rustlings hint functions1` for hints :)
+
+fn call_me() {}
+
+fn main() {
+    call_me();
+}
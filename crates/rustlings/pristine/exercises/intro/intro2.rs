@@ -0,0 +1,8 @@
+// intro2.rs
+// Make the code print a greeting to the world.
+// Execute `rustlings hint intro2` for a hint.
+
+fn main() {
+    let world = "World";
+    println!("Hello {}!", world);
+}
@@ -0,0 +1,2 @@
+// The code of the submodules lives in the front_of_house directory
+pub mod hosting;
@@ -0,0 +1,3 @@
+fn seat_at_table() -> String {
+    String::from("Right this way!")
+}
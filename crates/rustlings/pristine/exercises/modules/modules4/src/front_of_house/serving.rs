@@ -0,0 +1,3 @@
+pub fn take_order() -> String {
+    String::from("One pizza, coming up!")
+}
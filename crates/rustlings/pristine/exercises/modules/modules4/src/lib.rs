@@ -0,0 +1,26 @@
+// modules4
+// The restaurant has grown, so its modules are split over several files,
+// the way it's done in larger crates. Make the tests compile by declaring
+// the missing module and making the right items public.
+// Execute `rustlings hint modules4` for hints :)
+
+// I AM NOT DONE
+
+mod front_of_house;
+
+pub use front_of_house::hosting;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seats_guests() {
+        assert_eq!(hosting::seat_at_table(), "Right this way!");
+    }
+
+    #[test]
+    fn takes_orders() {
+        assert_eq!(front_of_house::serving::take_order(), "One pizza, coming up!");
+    }
+}
@@ -0,0 +1,14 @@
+// move_semantics6.rs
+// This time, make me fail to compile! Reorder the lines in `main()`, without
+// adding, changing or removing any of them, so that the compiler rejects the
+// program with error E0382: borrow of moved value.
+// Execute `rustlings hint move_semantics6` for hints :)
+
+// I AM NOT DONE
+
+fn main() {
+    let greeting = String::from("Hello");
+    println!("{greeting}");
+    let moved = greeting;
+    println!("{moved}");
+}
@@ -0,0 +1,4 @@
+// &'static is a "lifetime specifier", something you'll learn more about later
+pub fn hello() -> &'static str {
+    "Hello, World!"
+}
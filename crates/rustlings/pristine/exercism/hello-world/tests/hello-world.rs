@@ -0,0 +1,4 @@
+#[test]
+fn hello_world() {
+    assert_eq!("Hello, World!", hello_world::hello());
+}
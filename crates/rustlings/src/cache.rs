@@ -0,0 +1,270 @@
+use crate::diagnostic;
+use crate::exercise::Exercise;
+use crate::progress::hash_bytes;
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::sync::LazyLock;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+// Where the results of compiling exercises are kept, one directory
+// per exercise, holding one entry per build
+const CACHE_DIR: &str = "./target/rustlings/cache";
+// How many builds of every exercise are kept, so that going back to
+// a previous version of an exercise doesn't mean compiling it again
+const KEPT_BUILDS: usize = 3;
+// The files of a cache entry
+const ARTIFACT: &str = "artifact";
+const STDOUT: &str = "stdout";
+const STDERR: &str = "stderr";
+// Only present if the build succeeded
+const SUCCESS: &str = "success";
+
+// When this run started using the cache. The entries used since are never
+// pruned, since other threads of the run may be about to use them
+static RUN_START: LazyLock<SystemTime> = LazyLock::new(SystemTime::now);
+
+// The result of compiling an exercise with rustc, fresh or from the cache
+pub struct Build {
+    // Whether or not rustc succeeded
+    pub success: bool,
+    // What rustc printed
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    // The file rustc produced, if it succeeded
+    pub artifact: PathBuf,
+}
+
+// Compile the exercise with rustc and the given arguments, unless the same
+// source was already compiled the same way by the same compiler.
+// Compilation failures are cached too, along with their diagnostics, as long
+// as rustc reported errors: anything else, like a crash, may not happen again.
+pub fn rustc(exercise: &Exercise, args: &[&str]) -> io::Result<Build> {
+    LazyLock::force(&RUN_START);
+    let source = fs::read(&exercise.path)?;
+    let exercise_dir = Path::new(CACHE_DIR).join(&exercise.name);
+    let entry = exercise_dir.join(key(exercise, args, &source));
+    if let Some(build) = load(&entry) {
+        // Keep the entries that are in use from being pruned
+        let _ = File::open(&entry).and_then(|dir| dir.set_modified(SystemTime::now()));
+        return Ok(build);
+    }
+
+    // The build happens in a staging directory, which only becomes an
+    // entry once it's complete, so that concurrent runs never see
+    // a partial entry
+    let thread_id = format!("{:?}", std::thread::current().id());
+    let staging = exercise_dir.join(format!(
+        ".staging_{}_{}",
+        process::id(),
+        thread_id
+            .trim_start_matches("ThreadId(")
+            .trim_end_matches(')')
+    ));
+    fs::create_dir_all(&staging)?;
+    let output = Command::new("rustc")
+        .arg(&exercise.path)
+        .args(args)
+        .arg("-o")
+        .arg(staging.join(ARTIFACT))
+        .output()?;
+    fs::write(staging.join(STDOUT), &output.stdout)?;
+    fs::write(staging.join(STDERR), &output.stderr)?;
+    let success = output.status.success();
+    if success {
+        File::create(staging.join(SUCCESS))?;
+    }
+    let build = Build {
+        success,
+        stdout: output.stdout,
+        stderr: output.stderr,
+        artifact: entry.join(ARTIFACT),
+    };
+    if !success && !reports_errors(&build.stderr) {
+        let _ = fs::remove_dir_all(&staging);
+        return Ok(build);
+    }
+    if fs::rename(&staging, &entry).is_err() {
+        // Someone else just stored the same build
+        let _ = fs::remove_dir_all(&staging);
+    }
+    prune(&exercise_dir);
+    Ok(build)
+}
+
+// The build of the exercise as it is now, with the given arguments, if it's
+// in the cache. Nothing is compiled
+pub fn cached(exercise: &Exercise, args: &[&str]) -> Option<Build> {
+    let source = fs::read(&exercise.path).ok()?;
+    let exercise_dir = Path::new(CACHE_DIR).join(&exercise.name);
+    load(&exercise_dir.join(key(exercise, args, &source)))
+}
+
+// Whether or not rustc explained why the build failed
+fn reports_errors(stderr: &[u8]) -> bool {
+    diagnostic::parse(&String::from_utf8_lossy(stderr))
+        .iter()
+        .any(|diagnostic| diagnostic.is_error() && !diagnostic.is_summary())
+}
+
+// The cache key of a build: anything that can change what rustc produces
+fn key(exercise: &Exercise, args: &[&str], source: &[u8]) -> String {
+    let mut bytes = Vec::new();
+    for part in [rustc_version(), &exercise.path.to_string_lossy()]
+        .into_iter()
+        .chain(args.iter().copied())
+    {
+        bytes.extend_from_slice(part.as_bytes());
+        bytes.push(0);
+    }
+    bytes.extend_from_slice(source);
+    format!("{:016x}", hash_bytes(&bytes))
+}
+
+// The full version of rustc, which changes with every toolchain
+fn rustc_version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        Command::new("rustc")
+            .arg("-vV")
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default()
+    })
+}
+
+fn load(entry: &Path) -> Option<Build> {
+    Some(Build {
+        success: entry.join(SUCCESS).exists(),
+        stdout: fs::read(entry.join(STDOUT)).ok()?,
+        stderr: fs::read(entry.join(STDERR)).ok()?,
+        artifact: entry.join(ARTIFACT),
+    })
+}
+
+// Remove all but the most recently used builds of an exercise,
+// apart from those used during this run
+fn prune(exercise_dir: &Path) {
+    let Ok(entries) = fs::read_dir(exercise_dir) else {
+        return;
+    };
+    let mut builds: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with('.') {
+                return None;
+            }
+            Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+        })
+        .collect();
+    builds.sort_by_key(|build| Reverse(build.0));
+    for (used, build) in builds.into_iter().skip(KEPT_BUILDS) {
+        if used < *RUN_START {
+            let _ = fs::remove_dir_all(build);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise(path: &str) -> Exercise {
+        Exercise {
+            name: "cached".into(),
+            path: PathBuf::from(path),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_key_changes_with_everything_that_matters() {
+        let original = exercise("exercise.rs");
+        let moved = exercise("moved.rs");
+        let source = b"fn main() {}";
+        let reference = key(&original, &["--test"], source);
+        assert_eq!(reference, key(&original, &["--test"], source));
+        assert_ne!(reference, key(&original, &[], source));
+        assert_ne!(reference, key(&original, &["--test"], b"fn main() { }"));
+        assert_ne!(reference, key(&moved, &["--test"], source));
+    }
+
+    #[test]
+    fn test_unchanged_exercise_is_not_rebuilt() {
+        let exercise = Exercise {
+            name: "cached_finished_exercise".into(),
+            ..exercise("tests/fixture/state/finished_exercise.rs")
+        };
+        let first = rustc(&exercise, &[]).unwrap();
+        assert!(first.success);
+        let built = fs::metadata(&first.artifact).unwrap().modified().unwrap();
+        let second = rustc(&exercise, &[]).unwrap();
+        assert!(second.success);
+        assert_eq!(first.artifact, second.artifact);
+        assert_eq!(
+            built,
+            fs::metadata(&second.artifact).unwrap().modified().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_failures_are_cached_with_their_diagnostics() {
+        let exercise = Exercise {
+            name: "cached_broken_exercise".into(),
+            ..exercise("tests/fixture/failure/compFailure.rs")
+        };
+        let first = rustc(&exercise, diagnostic::RUSTC_JSON_ARGS).unwrap();
+        assert!(!first.success);
+        let cached = cached(&exercise, diagnostic::RUSTC_JSON_ARGS).unwrap();
+        assert!(!cached.success);
+        assert!(!cached.stderr.is_empty());
+        assert_eq!(first.stderr, cached.stderr);
+    }
+
+    #[test]
+    fn test_failures_without_diagnostics_are_not_cached() {
+        let exercise = Exercise {
+            name: "uncached_broken_exercise".into(),
+            ..exercise("tests/fixture/failure/compFailure.rs")
+        };
+        // Without JSON diagnostics, there's no telling why rustc failed
+        let build = rustc(&exercise, &[]).unwrap();
+        assert!(!build.success);
+        assert!(cached(&exercise, &[]).is_none());
+    }
+
+    #[test]
+    fn test_builds_of_this_run_are_not_pruned() {
+        let exercise_dir = Path::new(CACHE_DIR).join("pruned_exercise");
+        let _ = fs::remove_dir_all(&exercise_dir);
+        LazyLock::force(&RUN_START);
+        let earlier_run = SystemTime::UNIX_EPOCH;
+        let this_run = SystemTime::now();
+        let entries = [
+            ("old1", earlier_run),
+            ("new1", this_run),
+            ("new2", this_run),
+            ("old2", earlier_run),
+            ("new3", this_run),
+            ("new4", this_run),
+        ];
+        for (name, used) in entries {
+            let entry = exercise_dir.join(name);
+            fs::create_dir_all(&entry).unwrap();
+            File::open(&entry).unwrap().set_modified(used).unwrap();
+        }
+        prune(&exercise_dir);
+        let mut kept: Vec<String> = fs::read_dir(&exercise_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        kept.sort();
+        // The builds of this run are kept, even past the limit
+        assert_eq!(kept, ["new1", "new2", "new3", "new4"]);
+        fs::remove_dir_all(exercise_dir).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+use console::{style, Emoji};
+use indicatif::ProgressBar;
+use rustlings::exercise::{project_files, Exercise};
+use rustlings::outcome::{evaluate, Outcome};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Where the reference solutions are copied to, to check them
+const SOLUTIONS_DIR: &str = "./target/solutions";
+
+// What's wrong with an exercise, as far as its authors are concerned
+#[derive(PartialEq, Eq, Debug)]
+pub enum Problem {
+    // There is no reference solution for the exercise
+    MissingSolution,
+    // The reference solution doesn't compile
+    SolutionDoesNotCompile(String),
+    // The reference solution compiles, but fails when run or tested
+    SolutionFails(String),
+    // The exercise passes without the learner doing anything
+    ExerciseAlreadyPasses,
+}
+
+// Check every exercise: its reference solution must pass,
+// while the exercise as shipped must not.
+// Returns whether or not all exercises are fine.
+pub fn check(exercises: &[Exercise]) -> bool {
+    let mut failures = 0;
+    for exercise in exercises {
+        let progress_bar = ProgressBar::new_spinner();
+        progress_bar.set_message(format!("Checking {exercise}..."));
+        progress_bar.enable_steady_tick(Duration::from_millis(100));
+        let problems = check_exercise(exercise);
+        progress_bar.finish_and_clear();
+
+        match problems {
+            Ok(problems) if problems.is_empty() => {
+                println!("{} {}", mark(true), exercise);
+            }
+            Ok(problems) => {
+                failures += 1;
+                println!("{} {}", mark(false), exercise);
+                for problem in problems {
+                    print_problem(&problem);
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                println!("{} {}: {e}", mark(false), exercise);
+            }
+        }
+    }
+
+    println!();
+    if failures == 0 {
+        println!("All {} exercises are fine.", exercises.len());
+    } else {
+        println!("{failures} of {} exercises have problems.", exercises.len());
+    }
+    failures == 0
+}
+
+// The mark in front of a checked exercise, in ASCII where emojis are unwanted
+fn mark(fine: bool) -> String {
+    let (emoji, ascii) = if fine { ("✓", "ok") } else { ("✗", "x") };
+    let mark = if env::var("NO_EMOJI").is_ok() {
+        ascii.to_string()
+    } else {
+        Emoji(emoji, ascii).to_string()
+    };
+    match fine {
+        true => style(mark).green().to_string(),
+        false => style(mark).red().to_string(),
+    }
+}
+
+fn print_problem(problem: &Problem) {
+    match problem {
+        Problem::MissingSolution => println!("    there is no reference solution"),
+        Problem::SolutionDoesNotCompile(stderr) => {
+            println!("    the reference solution doesn't compile:");
+            println!("{stderr}");
+        }
+        Problem::SolutionFails(output) => {
+            println!("    the reference solution fails:");
+            println!("{output}");
+        }
+        Problem::ExerciseAlreadyPasses => {
+            println!("    the exercise already passes without any change")
+        }
+    }
+}
+
+// Check a single exercise, returning everything that's wrong with it
+pub fn check_exercise(exercise: &Exercise) -> io::Result<Vec<Problem>> {
+    let mut problems = Vec::new();
+
+    let solution = exercise.solution_path();
+    if solution.exists() {
+        // The solution is compiled from a scratch directory, exactly like
+        // the exercise would be, so that nothing in the exercises directory
+        // is touched. It's named apart from the exercise, so that the
+        // solution gets a clippy project and build cache of its own, and
+        // its path stays the same, so that the builds are reused across checks
+        let scratch = ScratchDir::new(&exercise.name)?;
+        let file_name = exercise.path.file_name().unwrap_or(exercise.name.as_ref());
+        let solved = Exercise {
+            name: format!("{}_solution", exercise.name),
+            path: scratch.0.join(file_name),
+            ..exercise.clone()
+        };
+        copy_solution(&solution, &solved.path)?;
+        match evaluate(&solved, None, || {}).0 {
+            Outcome::Success(_) => {}
+            Outcome::CompileFailure(output) => {
+                problems.push(Problem::SolutionDoesNotCompile(output.stderr))
+            }
+            Outcome::Error(error) => problems.push(Problem::SolutionFails(error.to_string())),
+            Outcome::NotRejected => problems.push(Problem::SolutionFails(format!(
+                "it compiles, but should be rejected with {}",
+                exercise.expected_error()
+            ))),
+            Outcome::RunFailure(output)
+            | Outcome::WrongOutput(output)
+            | Outcome::WrongError(output, _)
+            | Outcome::Timeout(output)
+            | Outcome::LimitExceeded(output, _) => problems.push(Problem::SolutionFails(format!(
+                "{}{}",
+                output.stdout, output.stderr
+            ))),
+        }
+    } else {
+        problems.push(Problem::MissingSolution);
+    }
+
+    if let Outcome::Success(_) = evaluate(exercise, None, || {}).0 {
+        problems.push(Problem::ExerciseAlreadyPasses);
+    }
+
+    Ok(problems)
+}
+
+// Copy the solution file, or every file of a Cargo project solution
+fn copy_solution(from: &Path, to: &Path) -> io::Result<()> {
+    if !from.is_dir() {
+        return fs::copy(from, to).map(|_| ());
+    }
+    for file in project_files(from)? {
+        let target = to.join(file.strip_prefix(from).unwrap_or(&file));
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&file, target)?;
+    }
+    Ok(())
+}
+
+// A scratch directory that is removed once it's dropped
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new(name: &str) -> io::Result<Self> {
+        let path = Path::new(SOLUTIONS_DIR).join(name);
+        fs::create_dir_all(&path)?;
+        Ok(ScratchDir(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ignored = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,397 @@
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The arguments making rustc emit its diagnostics as JSON, one per line.
+/// They're rendered by [`render`] rather than by rustc
+pub const RUSTC_JSON_ARGS: &[&str] = &["--error-format=json"];
+
+/// How many diagnostics are shown at most. Errors often cascade, and
+/// beginners are better off fixing the first ones before looking further
+pub const SHOWN_DIAGNOSTICS: usize = 3;
+
+/// A diagnostic emitted by rustc: an error or a warning, along with the
+/// notes, help and suggestions attached to it.
+/// Only the fields rustlings needs are deserialized
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Diagnostic {
+    /// What went wrong, e.g. "mismatched types"
+    #[serde(default)]
+    pub message: String,
+    /// The error code, if the diagnostic has one
+    pub code: Option<Code>,
+    /// How severe the diagnostic is, e.g. "error", "warning" or "help"
+    pub level: String,
+    /// The places in the source the diagnostic is about
+    #[serde(default)]
+    pub spans: Vec<Span>,
+    /// The notes and help attached to the diagnostic
+    #[serde(default)]
+    pub children: Vec<Diagnostic>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Code {
+    /// The code itself, e.g. "E0382"
+    pub code: String,
+}
+
+/// A place in the source a diagnostic is about
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Span {
+    pub file_name: String,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    /// Whether this is where the problem is, rather than some context
+    pub is_primary: bool,
+    /// The lines of source the span covers
+    #[serde(default)]
+    pub text: Vec<SpanLine>,
+    /// What the span is about, e.g. "expected `i32`, found `&str`"
+    #[serde(default)]
+    pub label: Option<String>,
+    /// The code the compiler suggests putting in place of the span
+    #[serde(default)]
+    pub suggested_replacement: Option<String>,
+}
+
+/// A line of source covered by a span
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SpanLine {
+    pub text: String,
+    /// The columns of the line the span starts and ends at, from 1
+    pub highlight_start: usize,
+    pub highlight_end: usize,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level == "error"
+    }
+
+    /// Whether or not the diagnostic only sums up the others, like
+    /// "aborting due to 2 previous errors"
+    pub fn is_summary(&self) -> bool {
+        self.spans.is_empty()
+            && self.code.is_none()
+            && (self.level == "failure-note"
+                || self.message.starts_with("aborting due to")
+                || (self.message.contains("warning") && self.message.ends_with("emitted")))
+    }
+
+    /// The code the compiler suggests writing, found in the help attached
+    /// to the diagnostic
+    pub fn suggestions(&self) -> Vec<&str> {
+        self.children
+            .iter()
+            .flat_map(|child| &child.spans)
+            .filter_map(|span| span.suggested_replacement.as_deref())
+            .collect()
+    }
+}
+
+/// Parse the JSON diagnostics rustc printed on stderr.
+/// Anything that isn't a diagnostic, like the output of a crashing compiler,
+/// is skipped.
+pub fn parse(stderr: &str) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// What cargo reported about a build with --message-format=json
+#[derive(Default, Debug)]
+pub struct CargoMessages {
+    /// The diagnostics of the compiler
+    pub diagnostics: Vec<Diagnostic>,
+    /// The binaries that were built
+    pub executables: Vec<PathBuf>,
+}
+
+// A single message printed by cargo.
+// Only the messages rustlings needs are deserialized
+#[derive(Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerMessage {
+        message: Diagnostic,
+    },
+    CompilerArtifact {
+        executable: Option<PathBuf>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Parse the JSON messages cargo printed on stdout
+pub fn parse_cargo_messages(stdout: &str) -> CargoMessages {
+    let mut messages = CargoMessages::default();
+    for line in stdout.lines() {
+        match serde_json::from_str(line) {
+            Ok(CargoMessage::CompilerMessage { message }) => messages.diagnostics.push(message),
+            Ok(CargoMessage::CompilerArtifact {
+                executable: Some(executable),
+            }) => messages.executables.push(executable),
+            _ => {}
+        }
+    }
+    messages
+}
+
+/// The codes of the errors among the diagnostics, without duplicates
+pub fn error_codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+    let mut codes = Vec::new();
+    for diagnostic in diagnostics.iter().filter(|d| d.is_error()) {
+        if let Some(code) = &diagnostic.code {
+            if !codes.contains(&code.code.as_str()) {
+                codes.push(code.code.as_str());
+            }
+        }
+    }
+    codes
+}
+
+/// Render the diagnostics for the end user: errors first, then warnings,
+/// showing at most `limit` of them along with the source they're about.
+/// Diagnostics that only sum up the others are left out
+pub fn render(diagnostics: &[Diagnostic], limit: usize) -> String {
+    let (errors, warnings): (Vec<&Diagnostic>, Vec<&Diagnostic>) = diagnostics
+        .iter()
+        .filter(|diagnostic| !diagnostic.is_summary())
+        .partition(|diagnostic| diagnostic.is_error());
+    let mut rendered = errors
+        .iter()
+        .chain(&warnings)
+        .take(limit)
+        .map(|diagnostic| render_diagnostic(diagnostic) + "\n")
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let hidden_errors = errors.len().saturating_sub(limit);
+    let hidden_warnings = warnings.len() - warnings.len().min(limit - errors.len().min(limit));
+    let hidden = match (hidden_errors, hidden_warnings) {
+        (0, 0) => return rendered,
+        (errors, 0) => plural(errors, "error"),
+        (0, warnings) => plural(warnings, "warning"),
+        (errors, warnings) => format!(
+            "{} and {}",
+            plural(errors, "error"),
+            plural(warnings, "warning")
+        ),
+    };
+    // Errors often follow from the first ones, warnings don't
+    let advice = match hidden_errors {
+        0 => "",
+        _ => " Fixing the first errors often fixes the others too.",
+    };
+    rendered.push_str(&format!(
+        "\n{}\n",
+        style(format!("... and {hidden}.{advice}")).dim()
+    ));
+    rendered
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 more {noun}"),
+        _ => format!("{count} more {noun}s"),
+    }
+}
+
+// Render a single diagnostic the way rustc does: a header, where the problem
+// is, the lines of source it's about with the spans underlined, then the
+// notes and help attached to it
+fn render_diagnostic(diagnostic: &Diagnostic) -> String {
+    let level = match diagnostic.level.as_str() {
+        "error" => style(diagnostic.level.as_str()).red().bold(),
+        "warning" => style(diagnostic.level.as_str()).yellow().bold(),
+        level => style(level).bold(),
+    };
+    let code = match &diagnostic.code {
+        Some(code) => format!("[{}]", code.code),
+        None => String::new(),
+    };
+    let mut lines = vec![format!(
+        "{}{}: {}",
+        level,
+        style(code).bold().red(),
+        style(&diagnostic.message).bold()
+    )];
+
+    // The line numbers and markers of every line of source
+    let mut excerpt: Vec<(usize, usize, &str, String, bool)> = Vec::new();
+    for span in &diagnostic.spans {
+        for (index, line) in span.text.iter().enumerate() {
+            let number = span.line_start + index;
+            let start = line.highlight_start.max(1);
+            let width = line.highlight_end.saturating_sub(start).max(1);
+            let marker = if span.is_primary { "^" } else { "-" };
+            let mut underline = format!("{}{}", " ".repeat(start - 1), marker.repeat(width));
+            if let (Some(label), true) = (&span.label, number == span.line_end) {
+                underline = format!("{underline} {label}");
+            }
+            excerpt.push((number, start, &line.text, underline, span.is_primary));
+        }
+    }
+    excerpt.sort_by_key(|(number, start, ..)| (*number, *start));
+    let gutter = excerpt
+        .iter()
+        .map(|(number, ..)| number.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let bar = style(format!("{:gutter$} |", "")).blue().bold();
+
+    let primary = diagnostic
+        .spans
+        .iter()
+        .find(|span| span.is_primary)
+        .or(diagnostic.spans.first());
+    if let Some(span) = primary {
+        // Clippy checks exercises through their absolute path
+        let current_dir = env::current_dir().unwrap_or_default();
+        let path = Path::new(&span.file_name);
+        lines.push(format!(
+            "{}{} {}:{}:{}",
+            " ".repeat(gutter),
+            style("-->").blue().bold(),
+            path.strip_prefix(current_dir).unwrap_or(path).display(),
+            span.line_start,
+            span.column_start
+        ));
+        lines.push(bar.to_string());
+    }
+    let mut previous = None;
+    for (number, _, text, underline, primary) in &excerpt {
+        if previous != Some(*number) {
+            let number = style(format!("{number:>gutter$} |")).blue().bold();
+            lines.push(format!("{number} {text}"));
+        }
+        let underline = match primary {
+            true => style(underline).red().bold(),
+            false => style(underline).blue().bold(),
+        };
+        lines.push(format!("{bar} {underline}"));
+        previous = Some(*number);
+    }
+
+    for child in &diagnostic.children {
+        let suggestions: Vec<String> = child
+            .spans
+            .iter()
+            .filter_map(|span| span.suggested_replacement.as_deref())
+            .map(|replacement| format!("`{}`", replacement.trim()))
+            .collect();
+        let suggestion = match suggestions.is_empty() {
+            true => String::new(),
+            false => format!(": {}", suggestions.join(" or ")),
+        };
+        lines.push(format!(
+            "{:gutter$} {} {}: {}{suggestion}",
+            "",
+            style("=").blue().bold(),
+            style(&child.level).bold(),
+            child.message
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const STDERR: &str = r#"{"$message_type":"diagnostic","message":"unused variable: `t`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[],"children":[]}
+{"$message_type":"diagnostic","message":"cannot assign twice to immutable variable `x`","code":{"code":"E0384","explanation":"..."},"level":"error","spans":[{"file_name":"exercises/x.rs","line_start":2,"line_end":2,"column_start":9,"column_end":10,"is_primary":false,"text":[{"text":"    let x = 5;","highlight_start":9,"highlight_end":10}],"label":"first assignment to `x`","suggested_replacement":null},{"file_name":"exercises/x.rs","line_start":3,"line_end":3,"column_start":5,"column_end":10,"is_primary":true,"text":[{"text":"    x = 6;","highlight_start":5,"highlight_end":10}],"label":"cannot assign twice to immutable variable","suggested_replacement":null}],"children":[{"message":"consider making this binding mutable","code":null,"level":"help","spans":[{"file_name":"exercises/x.rs","line_start":2,"line_end":2,"column_start":9,"column_end":9,"is_primary":true,"text":[],"label":null,"suggested_replacement":"mut "}],"children":[]}]}
+{"$message_type":"diagnostic","message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}
+{"$message_type":"diagnostic","message":"For more information about this error, try `rustc --explain E0384`.","code":null,"level":"failure-note","spans":[],"children":[]}
+"#;
+
+    fn rendered(diagnostics: &[Diagnostic], limit: usize) -> String {
+        console::strip_ansi_codes(&render(diagnostics, limit)).into_owned()
+    }
+
+    fn error(message: &str) -> Diagnostic {
+        Diagnostic {
+            message: message.to_string(),
+            code: None,
+            level: "error".to_string(),
+            spans: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let diagnostics = parse(STDERR);
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(error_codes(&diagnostics), ["E0384"]);
+        assert_eq!(diagnostics[1].spans.len(), 2);
+        assert_eq!(diagnostics[1].suggestions(), ["mut "]);
+        assert!(diagnostics[2].is_summary() && diagnostics[3].is_summary());
+    }
+
+    #[test]
+    fn test_render_errors_first_with_source() {
+        let diagnostics = parse(STDERR);
+        assert_eq!(
+            rendered(&diagnostics, SHOWN_DIAGNOSTICS),
+            "error[E0384]: cannot assign twice to immutable variable `x`
+ --> exercises/x.rs:3:5
+  |
+2 |     let x = 5;
+  |         - first assignment to `x`
+3 |     x = 6;
+  |     ^^^^^ cannot assign twice to immutable variable
+  = help: consider making this binding mutable: `mut`
+
+warning[unused_variables]: unused variable: `t`
+"
+        );
+    }
+
+    #[test]
+    fn test_render_is_limited() {
+        let diagnostics: Vec<Diagnostic> = ["first", "second", "third"]
+            .map(error)
+            .into_iter()
+            .collect();
+        assert_eq!(
+            rendered(&diagnostics, 1),
+            "error: first\n\n... and 2 more errors. Fixing the first errors often fixes the others too.\n"
+        );
+        let diagnostics = parse(STDERR);
+        assert_eq!(
+            rendered(&diagnostics, 1).lines().last(),
+            Some("... and 1 more warning.")
+        );
+    }
+
+    #[test]
+    fn test_parse_cargo_messages() {
+        let stdout = [
+            r#"{"reason":"compiler-message","package_id":"p","message":{"message":"unused variable: `t`","code":null,"level":"warning"}}"#,
+            r#"{"reason":"compiler-artifact","package_id":"p","executable":null}"#,
+            r#"{"reason":"compiler-artifact","package_id":"p","executable":"/tmp/target/debug/deps/p-0123"}"#,
+            r#"{"reason":"build-finished","success":true}"#,
+        ]
+        .join("\n");
+        let messages = parse_cargo_messages(&stdout);
+        assert_eq!(
+            rendered(&messages.diagnostics, SHOWN_DIAGNOSTICS),
+            "warning: unused variable: `t`\n"
+        );
+        assert_eq!(
+            messages.executables,
+            [PathBuf::from("/tmp/target/debug/deps/p-0123")]
+        );
+    }
+
+    #[test]
+    fn test_garbage_is_skipped() {
+        assert!(parse("thread 'rustc' panicked\n").is_empty());
+    }
+}
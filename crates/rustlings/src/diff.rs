@@ -0,0 +1,195 @@
+use crate::exercise::Exercise;
+use crate::pristine;
+use console::style;
+use similar::{ChangeTag, DiffTag, TextDiff};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// How many unchanged lines are shown around every change
+const CONTEXT_RADIUS: usize = 3;
+// The line starting the test module of an exercise
+const TEST_MODULE_MARKER: &str = "#[cfg(test)]";
+
+/// What the learner changed in an exercise
+pub struct ExerciseDiff {
+    /// The unified diff from the original exercise to the current one
+    pub unified: String,
+    /// Whether or not lines of the original test module were changed
+    pub touches_tests: bool,
+}
+
+/// Compare the exercise with its original version, as it was shipped, so
+/// that edits show up whether or not they were committed.
+/// Every original file of a Cargo project exercise is compared separately.
+/// Returns None if there is no original version of this exercise.
+pub fn diff(exercise: &Exercise) -> io::Result<Option<ExerciseDiff>> {
+    let originals = pristine::originals(exercise);
+    if originals.is_empty() {
+        return Ok(None);
+    }
+    compare(&originals, Path::new("")).map(Some)
+}
+
+// Compare the original files with the current ones, with their paths
+// relative to the given directory
+fn compare(originals: &[(&Path, &str)], root: &Path) -> io::Result<ExerciseDiff> {
+    let mut diff = ExerciseDiff {
+        unified: String::new(),
+        touches_tests: false,
+    };
+    for (path, original) in originals {
+        // A deleted file is shown as if it was emptied
+        let current = match fs::read_to_string(root.join(path)) {
+            Ok(current) => current,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let file_diff = diff_sources(&path.display().to_string(), original, &current);
+        diff.unified.push_str(&file_diff.unified);
+        diff.touches_tests |= file_diff.touches_tests;
+    }
+    Ok(diff)
+}
+
+fn diff_sources(label: &str, original: &str, current: &str) -> ExerciseDiff {
+    let tests_start = original
+        .lines()
+        .position(|line| line.trim() == TEST_MODULE_MARKER);
+
+    let text_diff = TextDiff::from_lines(original, current);
+    let mut unified = String::new();
+    for hunk in text_diff
+        .unified_diff()
+        .context_radius(CONTEXT_RADIUS)
+        .iter_hunks()
+    {
+        if unified.is_empty() {
+            unified.push_str(&format!(
+                "{}\n",
+                style(format!("--- {label} (original)")).bold()
+            ));
+            unified.push_str(&format!("{}\n", style(format!("+++ {label}")).bold()));
+        }
+        unified.push_str(&format!("{}\n", style(hunk.header()).cyan()));
+        for change in hunk.iter_changes() {
+            let line = format!("{}{}", change.tag(), change.value());
+            let line = line.trim_end_matches('\n');
+            let line = match change.tag() {
+                ChangeTag::Equal => style(line),
+                ChangeTag::Delete => style(line).red(),
+                ChangeTag::Insert => style(line).green(),
+            };
+            unified.push_str(&format!("{line}\n"));
+            if change.missing_newline() {
+                unified.push_str("\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    // Anything removed from, or inserted after the start of
+    // the original test module changes the tests
+    let touches_tests = tests_start.is_some_and(|start| {
+        text_diff
+            .ops()
+            .iter()
+            .any(|op| op.tag() != DiffTag::Equal && op.old_range().end > start)
+    });
+
+    ExerciseDiff {
+        unified,
+        touches_tests,
+    }
+}
+
+/// Compare two texts line by line, e.g. the expected and actual output
+/// of an exercise. Unlike a unified diff, every line is shown.
+pub fn diff_lines(old: &str, new: &str) -> String {
+    let mut lines = Vec::new();
+    for change in TextDiff::from_lines(old, new).iter_all_changes() {
+        let line = format!("{}{}", change.tag(), change.value());
+        let line = line.trim_end_matches('\n');
+        let line = match change.tag() {
+            ChangeTag::Equal => style(line),
+            ChangeTag::Delete => style(line).red(),
+            ChangeTag::Insert => style(line).green(),
+        };
+        lines.push(line.to_string());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::ExerciseList;
+    use std::process;
+
+    const ORIGINAL: &str = "fn main() {\n    todo!()\n}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn it_works() {}\n}\n";
+
+    #[test]
+    fn test_unchanged_exercise() {
+        let diff = diff_sources("ex.rs", ORIGINAL, ORIGINAL);
+        assert!(diff.unified.is_empty());
+        assert!(!diff.touches_tests);
+    }
+
+    #[test]
+    fn test_solution_does_not_touch_tests() {
+        let current = ORIGINAL.replace("todo!()", "println!(\"done\")");
+        let diff = diff_sources("ex.rs", ORIGINAL, &current);
+        assert!(diff.unified.contains("-    todo!()"));
+        assert!(diff.unified.contains("+    println!(\"done\")"));
+        assert!(!diff.touches_tests);
+    }
+
+    #[test]
+    fn test_edited_tests_are_detected() {
+        let current = ORIGINAL.replace("fn it_works() {}", "fn it_works() { return; }");
+        assert!(diff_sources("ex.rs", ORIGINAL, &current).touches_tests);
+
+        let current = ORIGINAL.replace("    #[test]\n", "    #[test]\n    #[ignore]\n");
+        assert!(diff_sources("ex.rs", ORIGINAL, &current).touches_tests);
+    }
+
+    #[test]
+    fn test_edits_are_compared_with_the_shipped_exercise() {
+        let info = fs::read_to_string("info.toml").unwrap();
+        let list = ExerciseList::load(&info, Path::new("")).unwrap();
+        let exercise = list
+            .exercises
+            .iter()
+            .find(|e| e.name == "from_into")
+            .unwrap();
+        let originals = pristine::originals(exercise);
+        let (path, original) = originals[0];
+        let shipped = fs::read_to_string(pristine::snapshot(path)).unwrap();
+        assert_eq!(original, shipped);
+
+        let scratch = std::env::temp_dir().join(format!("rustlings_diff_{}", process::id()));
+        fs::create_dir_all(scratch.join(path.parent().unwrap())).unwrap();
+        fs::write(scratch.join(path), original).unwrap();
+        assert!(compare(&originals, &scratch).unwrap().unified.is_empty());
+
+        fs::write(scratch.join(path), format!("// my edit\n{original}")).unwrap();
+        let diff = compare(&originals, &scratch).unwrap();
+        let unified = console::strip_ansi_codes(&diff.unified);
+        assert!(unified.contains("+// my edit"));
+        assert!(!diff.touches_tests);
+
+        let edited = original.replace("assert_eq!(dp.age, 30);", "");
+        fs::write(scratch.join(path), edited).unwrap();
+        let diff = compare(&originals, &scratch).unwrap();
+        let unified = console::strip_ansi_codes(&diff.unified);
+        assert!(unified.contains("-        assert_eq!(dp.age, 30);"));
+        assert!(diff.touches_tests);
+        fs::remove_dir_all(scratch).unwrap();
+    }
+
+    #[test]
+    fn test_diff_lines_shows_every_line() {
+        let diff = diff_lines("Hello\nWorld\n", "Hello\nworld\n");
+        let diff = console::strip_ansi_codes(&diff);
+        assert_eq!(diff, " Hello\n-World\n+world");
+    }
+}
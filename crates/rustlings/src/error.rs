@@ -0,0 +1,123 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+/// Everything that can keep a rustlings command from succeeding.
+/// Every kind of error exits with its own code, so that scripts can tell
+/// an exercise that doesn't compile from rustlings being misconfigured:
+///
+/// - 0: everything went fine
+/// - 1: an exercise failed: its tests or binary failed, it printed the wrong
+///   output, or it still has its `I AM NOT DONE` marker
+/// - 2: an exercise doesn't compile
+/// - 3: an exercise ran for longer than it's allowed to
+/// - 4: the command line doesn't make sense, e.g. an unknown exercise
+/// - 5: rustlings is misconfigured: info.toml or the progress file is broken,
+///   or it isn't run from the rustlings directory
+/// - 6: rustc or cargo can't be found
+/// - 7: a file couldn't be read or written
+#[derive(Debug)]
+pub enum Error {
+    /// An exercise failed after compiling
+    TestFailure(String),
+    /// An exercise doesn't compile
+    CompileFailure(String),
+    /// An exercise was killed for running too long
+    Timeout(String),
+    /// The command line asks for something that doesn't exist or can't be done
+    Usage(String),
+    /// info.toml, the progress file or the working directory is wrong
+    Config(String),
+    /// A tool rustlings runs exercises with is missing
+    ToolchainMissing(&'static str),
+    /// What rustlings was doing, and the I/O error that stopped it
+    Io(String, io::Error),
+}
+
+impl Error {
+    /// The code the process exits with
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::TestFailure(_) => 1,
+            Error::CompileFailure(_) => 2,
+            Error::Timeout(_) => 3,
+            Error::Usage(_) => 4,
+            Error::Config(_) => 5,
+            Error::ToolchainMissing(_) => 6,
+            Error::Io(..) => 7,
+        }
+    }
+
+    /// Whether or not this is about an exercise, which was already reported
+    /// to the end user along with the compiler or test output
+    pub fn is_reported(&self) -> bool {
+        matches!(
+            self,
+            Error::TestFailure(_) | Error::CompileFailure(_) | Error::Timeout(_)
+        )
+    }
+
+    /// The error of running a tool: either it's missing, or something else
+    /// went wrong
+    pub fn spawn(tool: &'static str, error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::NotFound {
+            Error::ToolchainMissing(tool)
+        } else {
+            Error::Io(format!("run {tool}"), error)
+        }
+    }
+
+    /// An I/O error, along with what rustlings was trying to do
+    pub fn io(action: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let action = action.into();
+        move |error| Error::Io(action, error)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TestFailure(exercise) => write!(f, "{exercise} failed"),
+            Error::CompileFailure(exercise) => write!(f, "{exercise} doesn't compile"),
+            Error::Timeout(exercise) => write!(f, "{exercise} ran for too long"),
+            Error::Usage(message) | Error::Config(message) => write!(f, "{message}"),
+            Error::ToolchainMissing(tool) => write!(
+                f,
+                "We cannot find `{tool}`.\nTry running `{tool} --version` to diagnose your problem.\nFor instructions on how to install Rust, check the README."
+            ),
+            Error::Io(action, error) => write!(f, "Failed to {action}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            Error::TestFailure("intro1".into()),
+            Error::CompileFailure("intro1".into()),
+            Error::Timeout("intro1".into()),
+            Error::Usage("No exercise found for 'intro9'!".into()),
+            Error::Config("info.toml is invalid".into()),
+            Error::ToolchainMissing("rustc"),
+            Error::Io("read info.toml".into(), io::ErrorKind::NotFound.into()),
+        ];
+        let codes: HashSet<i32> = errors.iter().map(Error::exit_code).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&0));
+    }
+
+    #[test]
+    fn test_missing_tool() {
+        let error = Error::spawn("cargo", io::ErrorKind::NotFound.into());
+        assert_eq!(error.exit_code(), 6);
+        assert!(error.to_string().starts_with("We cannot find `cargo`."));
+        let error = Error::spawn("cargo", io::ErrorKind::PermissionDenied.into());
+        assert!(error.to_string().starts_with("Failed to run cargo: "));
+    }
+}
@@ -1,100 +1,516 @@
+use crate::cache;
+use crate::diagnostic::{self, Diagnostic, RUSTC_JSON_ARGS, SHOWN_DIAGNOSTICS};
+use crate::error::Error;
+use crate::sandbox::{Sandbox, Violation};
+use crate::supervisor::output_with_timeout;
 use regex::Regex;
-use serde::Deserialize;
-use std::env;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
 use std::fmt::{self, Display, Formatter};
-use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
+use std::fs::{self, remove_dir_all};
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command};
+use std::time::Duration;
 
-const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
 const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
 const CONTEXT: usize = 2;
-const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
+// Where the Cargo projects clippy checks the exercises in are generated.
+// Every clippy exercise has its own project, kept between runs
+const CLIPPY_PROJECTS_DIR: &str = "./target/clippy";
+// The edition clippy exercises are checked with, unless their info.toml
+// entry says otherwise. Other exercises get rustc's default
+const CLIPPY_EDITION: &str = "2018";
+// The lints clippy exercises are checked with, unless their info.toml entry
+// lists others
+const DEFAULT_LINTS: &[&str] = &["-D", "clippy::float_cmp"];
+// Where Cargo project exercises are built. It's kept between runs, so that
+// their dependencies are only built once. Every worker thread evaluating
+// exercises concurrently has a directory of its own, as cargo locks it
+const CARGO_TARGET_DIR: &str = "./target/exercises";
+// The registry that the dependencies of Cargo project exercises are resolved
+// from, without network access. Created with `cargo vendor`
+const VENDOR_DIR: &str = "./vendor";
+// How long an exercise may run, unless its info.toml entry says otherwise
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+/// The topic Exercism crates are listed in
+pub const EXERCISM_TOPIC: &str = "exercism";
 
-// Get a temporary file name that is hopefully unique
+// Get a scratch directory name that is hopefully unique.
+// Every thread gets its own directory, so that exercises can be
+// compiled and run concurrently without stepping on each other.
 #[inline]
-fn temp_file() -> String {
+fn temp_dir() -> PathBuf {
     let thread_id: String = format!("{:?}", std::thread::current().id())
         .chars()
         .filter(|c| c.is_alphanumeric())
         .collect();
 
-    format!("./temp_{}_{}", process::id(), thread_id)
+    PathBuf::from(format!("./temp_{}_{}", process::id(), thread_id))
 }
 
-// The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+/// The mode of the exercise.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
-    // Indicates that the exercise should be compiled as a binary
+    /// Indicates that the exercise should be compiled as a binary
+    #[default]
     Compile,
-    // Indicates that the exercise should be compiled as a test harness
+    /// Indicates that the exercise should be compiled as a test harness
     Test,
-    // Indicates that the exercise should be linted with clippy
+    /// Indicates that the exercise should be linted with clippy
     Clippy,
+    /// Indicates that the exercise should be rejected by the compiler
+    #[serde(rename = "compile_fail")]
+    CompileFail,
+    /// Indicates that the exercise is a Cargo project whose tests should pass
+    Cargo,
+    /// Indicates that the exercise is an Exercism crate whose tests should
+    /// all pass, ignored ones included
+    Exercism,
 }
 
+/// Every exercise, in the order they're meant to be done,
+/// and the topics they're grouped in
 #[derive(Deserialize)]
+#[serde(from = "ExerciseListFile")]
 pub struct ExerciseList {
+    pub topics: Vec<Topic>,
+    /// The exercises of every topic, followed by those outside of any topic
     pub exercises: Vec<Exercise>,
+    /// The directory the Exercism crates are in, if any. They're only listed
+    /// once they're found by [`ExerciseList::load`]
+    pub exercism: Option<PathBuf>,
+}
+
+impl ExerciseList {
+    /// Parse info.toml, then list the Exercism crates found in the directory
+    /// it names. `root` is the directory info.toml is in: the paths of the
+    /// list stay relative to it, as they're written in info.toml
+    pub fn load(info: &str, root: &Path) -> Result<Self, toml::de::Error> {
+        let mut list: ExerciseList = toml::from_str(info)?;
+        let Some(dir) = list.exercism.clone() else {
+            return Ok(list);
+        };
+        // Exercism crates come last, in a topic of their own
+        let found = exercism_exercises(root, &dir);
+        if !found.is_empty() {
+            list.topics.push(Topic {
+                name: EXERCISM_TOPIC.into(),
+                readme: dir.join("README.md"),
+                dir,
+                exercises: found.iter().map(|e| e.name.clone()).collect(),
+                requires: Vec::new(),
+                gates: false,
+            });
+            list.exercises.extend(found);
+        }
+        Ok(list)
+    }
+}
+
+/// A group of exercises about the same subject
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub name: String,
+    /// The directory the exercises of the topic are in
+    pub dir: PathBuf,
+    /// The introduction to the topic
+    pub readme: PathBuf,
+    /// The names of the exercises of the topic, in order
+    pub exercises: Vec<String>,
+    /// The exercises and topics that have to be done before this topic is
+    /// unlocked, by name
+    pub requires: Vec<String>,
+    /// Whether or not this topic has to be done before any of the topics
+    /// after it is unlocked, like a quiz
+    pub gates: bool,
+}
+
+impl Topic {
+    /// Whether or not the exercise is listed in this topic
+    pub fn contains(&self, exercise: &Exercise) -> bool {
+        self.exercises.contains(&exercise.name)
+    }
+}
+
+// The exercise list as it's written in info.toml
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExerciseListFile {
+    #[serde(default)]
+    topics: Vec<TopicEntry>,
+    #[serde(default)]
+    exercises: Vec<Exercise>,
+    // The directory every Exercism crate is in, if any
+    #[serde(default)]
+    exercism: Option<PathBuf>,
 }
 
-// A representation of a rustlings exercise.
-// This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TopicEntry {
+    name: String,
+    // Defaults to exercises/<name>
+    #[serde(default)]
+    dir: Option<PathBuf>,
+    // Defaults to README.md in the directory of the topic
+    #[serde(default)]
+    readme: Option<PathBuf>,
+    #[serde(default)]
+    requires: Vec<String>,
+    // Whether or not the following topics require this one
+    #[serde(default)]
+    gates: bool,
+    exercises: Vec<Exercise>,
+}
+
+impl From<ExerciseListFile> for ExerciseList {
+    fn from(file: ExerciseListFile) -> Self {
+        let mut topics = Vec::new();
+        let mut exercises = Vec::new();
+        // The last gating topic so far, which has to be done before moving on
+        let mut gate: Option<String> = None;
+        for entry in file.topics {
+            let dir = entry
+                .dir
+                .unwrap_or_else(|| Path::new("exercises").join(&entry.name));
+            let mut requires = entry.requires;
+            if let Some(gate) = &gate {
+                if !requires.contains(gate) {
+                    requires.push(gate.clone());
+                }
+            }
+            if entry.gates {
+                gate = Some(entry.name.clone());
+            }
+            exercises.extend(entry.exercises.iter().map(|exercise| Exercise {
+                topic: Some(entry.name.clone()),
+                requires: requires.iter().chain(&exercise.requires).cloned().collect(),
+                ..exercise.clone()
+            }));
+            topics.push(Topic {
+                readme: entry.readme.unwrap_or_else(|| dir.join("README.md")),
+                dir,
+                exercises: entry.exercises.into_iter().map(|e| e.name).collect(),
+                requires,
+                gates: entry.gates,
+                name: entry.name,
+            });
+        }
+        exercises.extend(file.exercises);
+        ExerciseList {
+            topics,
+            exercises,
+            exercism: file.exercism,
+        }
+    }
+}
+
+// Every Exercism crate in the given directory, relative to the root, in
+// alphabetical order. Their README is their only hint. A directory that
+// can't be read simply doesn't have any exercise.
+fn exercism_exercises(root: &Path, dir: &Path) -> Vec<Exercise> {
+    let Ok(entries) = fs::read_dir(root.join(dir)) else {
+        return Vec::new();
+    };
+    let mut crates: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(dir.join(entry.ok()?.file_name())))
+        .filter(|path| root.join(path).join("Cargo.toml").is_file())
+        .collect();
+    crates.sort();
+    crates
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let readme = fs::read_to_string(root.join(&path).join("README.md"));
+            Some(Exercise {
+                hints: vec![readme.unwrap_or_default()],
+                name,
+                path,
+                mode: Mode::Exercism,
+                topic: Some(EXERCISM_TOPIC.into()),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// A representation of a rustlings exercise.
+/// This is deserialized from the accompanying info.toml file.
+/// The default is a compile mode exercise with neither a name nor a path,
+/// for the fields that matter to be filled in
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Exercise {
-    // Name of the exercise
+    /// Name of the exercise
     pub name: String,
-    // The path to the file containing the exercise's source code
+    /// The path to the file containing the exercise's source code,
+    /// or to the directory of a Cargo project exercise
     pub path: PathBuf,
-    // The mode of the exercise (Test, Compile, or Clippy)
+    /// The mode of the exercise (Test, Compile, Clippy, CompileFail, or Cargo)
     pub mode: Mode,
-    // The hint text associated with the exercise
-    pub hint: String,
+    /// The hints associated with the exercise, ordered from a gentle nudge
+    /// to a near-solution. A single hint can be given as a plain string
+    #[serde(rename = "hint", deserialize_with = "one_or_many")]
+    pub hints: Vec<String>,
+    /// How many seconds the exercise may run before being killed
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// The path to the reference solution, if it isn't the path of the
+    /// exercise with `exercises/` replaced by `solutions/`
+    #[serde(default)]
+    pub solution: Option<PathBuf>,
+    /// What the binary of a compile mode exercise has to print to pass
+    #[serde(default)]
+    pub expected_stdout: Option<ExpectedOutput>,
+    /// The standard input fed to the binary of the exercise, whatever its mode,
+    /// test harnesses included
+    #[serde(default)]
+    pub stdin: Option<String>,
+    /// The command line arguments passed to the binary of a compile or clippy
+    /// mode exercise. Test harnesses are run with arguments of their own
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The environment variables set for the binary of a compile or clippy
+    /// mode exercise
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// The code of the error a compile_fail exercise has to be rejected with,
+    /// e.g. "E0382". Without it, any error will do
+    #[serde(default)]
+    pub expect_error: Option<String>,
+    /// The edition the exercise is compiled with, e.g. "2021". Without it,
+    /// clippy exercises use 2018 and the others rustc's default.
+    /// Cargo project exercises declare theirs in their Cargo.toml instead
+    #[serde(default)]
+    pub edition: Option<String>,
+    /// The lints a clippy exercise is checked with, by name, on top of every
+    /// warning being denied, e.g. `lints = { "clippy::pedantic" = "deny" }`
+    #[serde(default)]
+    pub lints: Option<BTreeMap<String, LintLevel>>,
+    /// The exercises and topics that have to be done before this exercise is
+    /// unlocked, by name. Those of its topic are added when the list is loaded
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// The topic the exercise is listed in, if any
+    #[serde(skip)]
+    pub topic: Option<String>,
 }
 
-// An enum to track of the state of an Exercise.
-// An Exercise can be either Done or Pending
+/// How the compiler treats a lint
+#[derive(Deserialize, Copy, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    // The command line flag setting a lint to this level
+    fn flag(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "-A",
+            LintLevel::Warn => "-W",
+            LintLevel::Deny => "-D",
+            LintLevel::Forbid => "-F",
+        }
+    }
+}
+
+/// The standard output expected from an exercise.
+/// This is either a plain string, or a table like `{ regex = "..." }`
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ExpectedOutput {
+    /// Exactly this output, apart from trailing newlines
+    Exact(String),
+    /// Any output the regular expression matches somewhere
+    Pattern {
+        #[serde(deserialize_with = "regex")]
+        regex: Regex,
+    },
+}
+
+impl ExpectedOutput {
+    /// Check the output of the binary against the expectation
+    pub fn matches(&self, stdout: &str) -> bool {
+        match self {
+            ExpectedOutput::Exact(expected) => {
+                expected.replace("\r\n", "\n").trim_end_matches('\n')
+                    == stdout.replace("\r\n", "\n").trim_end_matches('\n')
+            }
+            ExpectedOutput::Pattern { regex } => regex.is_match(stdout),
+        }
+    }
+}
+
+fn regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    Regex::new(&pattern).map_err(serde::de::Error::custom)
+}
+
+// Accept either a single string or a list of strings
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(hint) => vec![hint],
+        OneOrMany::Many(hints) => hints,
+    })
+}
+
+/// An enum to track of the state of an Exercise.
+/// An Exercise can be either Done or Pending
 #[derive(PartialEq, Eq, Debug)]
 pub enum State {
-    // The state of the exercise once it's been completed
+    /// The state of the exercise once it's been completed
     Done,
-    // The state of the exercise while it's not completed yet
+    /// The state of the exercise while it's not completed yet
     Pending(Vec<ContextLine>),
 }
 
-// The context information of a pending exercise
+/// The context information of a pending exercise
 #[derive(PartialEq, Eq, Debug)]
 pub struct ContextLine {
-    // The source code that is still pending completion
+    /// The source code that is still pending completion
     pub line: String,
-    // The line number of the source code still pending completion
+    /// The line number of the source code still pending completion
     pub number: usize,
-    // Whether or not this is important
+    /// Whether or not this is important
     pub important: bool,
 }
 
-// The result of compiling an exercise
+/// The result of compiling an exercise
 pub struct CompiledExercise<'a> {
     exercise: &'a Exercise,
+    // The binaries to run, in order. Cargo projects can have several
+    // test harnesses, every other exercise has a single binary
+    binaries: Vec<PathBuf>,
     _handle: FileHandle,
 }
 
 impl CompiledExercise<'_> {
-    // Run the compiled exercise
-    pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+    /// Run the compiled exercise, confined to the sandbox if one is given.
+    /// The binaries are run one after the other, until one of them fails
+    pub fn run(&self, sandbox: Option<&Sandbox>) -> Result<ExerciseOutput, RunFailure> {
+        let mut output = ExerciseOutput::default();
+        for binary in &self.binaries {
+            match self.exercise.run(binary, sandbox) {
+                Ok(next) => output.append(next),
+                Err(mut failure) => {
+                    if let Some(failed) = failure.output_mut() {
+                        output.append(mem::take(failed));
+                        *failed = output;
+                    }
+                    return Err(failure);
+                }
+            }
+        }
+        Ok(output)
     }
 }
 
-// A representation of an already executed binary
-#[derive(Debug)]
+/// A representation of an already executed binary
+#[derive(Default, Debug)]
 pub struct ExerciseOutput {
-    // The textual contents of the standard output of the binary
+    /// The textual contents of the standard output of the binary
     pub stdout: String,
-    // The textual contents of the standard error of the binary
+    /// The textual contents of the standard error of the binary
     pub stderr: String,
+    /// The diagnostics of the compiler, when it rejected the exercise
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ExerciseOutput {
+    // The output of a compiler that rejected an exercise: its diagnostics,
+    // rendered on stderr. Anything the tool reported outside of a diagnostic,
+    // like cargo about a missing dependency, is shown when there's no
+    // diagnostic to show instead
+    fn diagnosed(diagnostics: Vec<Diagnostic>, stderr: &[u8]) -> Self {
+        let diagnostics: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|diagnostic| !diagnostic.is_summary())
+            .collect();
+        let stderr = match diagnostics.is_empty() {
+            true => String::from_utf8_lossy(stderr).to_string(),
+            false => diagnostic::render(&diagnostics, SHOWN_DIAGNOSTICS),
+        };
+        ExerciseOutput {
+            stdout: String::new(),
+            stderr,
+            diagnostics,
+        }
+    }
+
+    // Add what another binary printed after this output
+    fn append(&mut self, other: ExerciseOutput) {
+        self.stdout.push_str(&other.stdout);
+        self.stderr.push_str(&other.stderr);
+    }
+}
+
+/// The ways compiling an exercise can fail
+#[derive(Debug)]
+pub enum CompileError {
+    /// The compiler rejected the exercise
+    Rejected(ExerciseOutput),
+    /// The exercise is meant to be rejected, so it never gets a binary.
+    /// This is what [`Exercise::compile_to_fail`] made of it
+    MeantToBeRejected(Rejection),
+    /// The compiler couldn't be run at all
+    Error(Error),
+}
+
+/// What happened when an exercise that's meant to be rejected was compiled
+#[derive(Debug)]
+pub enum Rejection {
+    /// The compiler rejected the exercise with the expected error
+    Expected(ExerciseOutput),
+    /// The compiler rejected the exercise, but with other errors.
+    /// The codes of those errors are given
+    Unexpected(ExerciseOutput, Vec<String>),
+    /// The compiler accepted the exercise
+    Accepted,
+    /// The compiler couldn't be run at all
+    Error(Error),
+}
+
+/// The ways running a compiled exercise can fail
+#[derive(Debug)]
+pub enum RunFailure {
+    /// The binary exited unsuccessfully
+    Failed(ExerciseOutput),
+    /// The binary was killed after running for longer than its timeout
+    TimedOut(ExerciseOutput),
+    /// The binary exited successfully, but didn't print what it was expected to
+    WrongOutput(ExerciseOutput),
+    /// The binary ran into one of the limits of the sandbox
+    LimitExceeded(ExerciseOutput, Violation),
+    /// The binary couldn't be run at all
+    Error(Error),
+}
+
+impl RunFailure {
+    fn output_mut(&mut self) -> Option<&mut ExerciseOutput> {
+        match self {
+            RunFailure::Failed(output)
+            | RunFailure::TimedOut(output)
+            | RunFailure::WrongOutput(output)
+            | RunFailure::LimitExceeded(output, _) => Some(output),
+            RunFailure::Error(_) => None,
+        }
+    }
 }
 
 struct FileHandle;
@@ -106,113 +522,375 @@ impl Drop for FileHandle {
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
-        let cmd = match self.mode {
-            Mode::Compile => Command::new("rustc")
-                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
-                .output(),
-            Mode::Test => Command::new("rustc")
-                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
-                .output(),
-            Mode::Clippy => {
-                let cargo_toml = format!(
-                    r#"[package]
-name = "{}"
+    /// Compile the exercise into the binaries it's run with.
+    /// compile_fail exercises never get a binary: they're checked with
+    /// [`Exercise::compile_to_fail`] instead, whose verdict is the error.
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, CompileError> {
+        fs::create_dir_all(temp_dir())
+            .map_err(Error::io("create the build directory"))
+            .map_err(CompileError::Error)?;
+        let build = match self.mode {
+            Mode::Cargo | Mode::Exercism => return self.compile_project(),
+            Mode::Compile | Mode::Test => {
+                cache::rustc(self, &self.rustc_args()).map_err(|e| Error::spawn("rustc", e))
+            }
+            Mode::CompileFail => {
+                return Err(CompileError::MeantToBeRejected(self.compile_to_fail()))
+            }
+            Mode::Clippy => self.clippy(),
+        }
+        .map_err(CompileError::Error)?;
+
+        if build.success {
+            Ok(CompiledExercise {
+                exercise: self,
+                binaries: vec![build.artifact],
+                _handle: FileHandle,
+            })
+        } else {
+            clean();
+            // Clippy is run through cargo, which reports on stdout
+            let diagnostics = match self.mode {
+                Mode::Clippy => {
+                    diagnostic::parse_cargo_messages(&String::from_utf8_lossy(&build.stdout))
+                        .diagnostics
+                }
+                _ => diagnostic::parse(&String::from_utf8_lossy(&build.stderr)),
+            };
+            Err(CompileError::Rejected(ExerciseOutput::diagnosed(
+                diagnostics,
+                &build.stderr,
+            )))
+        }
+    }
+
+    // The arguments rustc builds the binary of the exercise with
+    fn rustc_args(&self) -> Vec<&str> {
+        let test: &[&str] = match self.mode {
+            Mode::Test => &["--test"],
+            _ => &[],
+        };
+        [test, &self.edition_args(), RUSTC_JSON_ARGS].concat()
+    }
+
+    /// Whether the exercise compiles as it is now, if that's known without
+    /// compiling it: only the builds cached by earlier runs are looked at.
+    /// Cargo keeps track of the builds of Cargo projects itself, and
+    /// compile_fail exercises aren't meant to compile, so neither is known
+    pub fn compiles(&self) -> Option<bool> {
+        match self.mode {
+            Mode::Compile | Mode::Test | Mode::Clippy => {
+                cache::cached(self, &self.rustc_args()).map(|build| build.success)
+            }
+            Mode::CompileFail | Mode::Cargo | Mode::Exercism => None,
+        }
+    }
+
+    // Check a clippy exercise, along with building its binary
+    fn clippy(&self) -> Result<cache::Build, Error> {
+        let project = self
+            .clippy_project()
+            .map_err(Error::io(format!("write the clippy project of {self}")))?;
+        // To support the ability to run the clippy exercises, build
+        // an executable, in addition to running clippy. With a
+        // compilation failure, this would silently fail. But we expect
+        // clippy to reflect the same failure while compiling later.
+        let build = cache::rustc(self, &self.rustc_args()).map_err(|e| Error::spawn("rustc", e))?;
+        let output = Command::new("cargo")
+            .arg("clippy")
+            .arg("--manifest-path")
+            .arg(project.join("Cargo.toml"))
+            .args(["--quiet", "--message-format=json"])
+            .arg("--")
+            .args(self.lint_args())
+            .output()
+            .map_err(|e| Error::spawn("cargo", e))?;
+        Ok(cache::Build {
+            success: output.status.success(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+            artifact: build.artifact,
+        })
+    }
+
+    // Generate the Cargo project clippy checks the exercise in.
+    // The project is kept between runs, so that clippy only has to check
+    // the exercise again once it has changed.
+    fn clippy_project(&self) -> io::Result<PathBuf> {
+        let project = Path::new(CLIPPY_PROJECTS_DIR).join(&self.name);
+        fs::create_dir_all(&project)?;
+        let package: String = self
+            .name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        let path = fs::canonicalize(&self.path)?;
+        let cargo_toml = format!(
+            r#"[package]
+name = "{package}"
 version = "0.0.1"
-edition = "2018"
+edition = "{}"
+
 [[bin]]
-name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
-                );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
-                    "Failed to write Clippy Cargo.toml file."
-                } else {
-                    "Failed to write 📎 Clippy 📎 Cargo.toml file."
-                };
-                fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
-                // To support the ability to run the clippy exercises, build
-                // an executable, in addition to running clippy. With a
-                // compilation failure, this would silently fail. But we expect
-                // clippy to reflect the same failure while compiling later.
-                Command::new("rustc")
-                    .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
-                    .expect("Failed to compile!");
-                // Due to an issue with Clippy, a cargo clean is required to catch all lints.
-                // See https://github.com/rust-lang/rust-clippy/issues/2604
-                // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
-                // https://github.com/rust-lang/rust-clippy/issues/3837
-                Command::new("cargo")
-                    .args(["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
-                    .expect("Failed to run 'cargo clean'");
-                Command::new("cargo")
-                    .args(["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(["--", "-D", "warnings", "-D", "clippy::float_cmp"])
-                    .output()
+name = "{package}"
+path = {}
+
+[workspace]
+"#,
+            self.edition().unwrap_or(CLIPPY_EDITION),
+            toml::Value::String(path.to_string_lossy().into_owned())
+        );
+        // Rewriting the manifest would make cargo check everything again
+        let manifest = project.join("Cargo.toml");
+        if fs::read_to_string(&manifest).ok().as_deref() != Some(cargo_toml.as_str()) {
+            fs::write(&manifest, cargo_toml)?;
+        }
+        Ok(project)
+    }
+
+    // The arguments passed to clippy to set the lints of a clippy exercise
+    fn lint_args(&self) -> Vec<&str> {
+        let mut args = vec!["-D", "warnings"];
+        match &self.lints {
+            None => args.extend(DEFAULT_LINTS),
+            Some(lints) => {
+                for (lint, level) in lints {
+                    args.extend([level.flag(), lint.as_str()]);
+                }
             }
         }
-        .expect("Failed to run 'compile' command.");
+        args
+    }
+
+    // Build the test harnesses of a Cargo project exercise
+    fn compile_project(&self) -> Result<CompiledExercise<'_>, CompileError> {
+        let cmd = cargo_command("test", &self.path)
+            .args(["--no-run", "--message-format=json"])
+            .output()
+            .map_err(|e| CompileError::Error(Error::spawn("cargo", e)))?;
+        let messages = diagnostic::parse_cargo_messages(&String::from_utf8_lossy(&cmd.stdout));
 
         if cmd.status.success() {
             Ok(CompiledExercise {
                 exercise: self,
+                binaries: messages.executables,
                 _handle: FileHandle,
             })
         } else {
             clean();
-            Err(ExerciseOutput {
-                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
-            })
+            Err(CompileError::Rejected(ExerciseOutput::diagnosed(
+                messages.diagnostics,
+                &cmd.stderr,
+            )))
         }
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        let arg = match self.mode {
-            Mode::Test => "--show-output",
-            _ => "",
+    fn run(&self, binary: &Path, sandbox: Option<&Sandbox>) -> Result<ExerciseOutput, RunFailure> {
+        let mut command = match sandbox {
+            None => {
+                let mut command = Command::new(binary);
+                // Tests of Cargo projects expect to run from the project
+                if let Mode::Cargo | Mode::Exercism = self.mode {
+                    command.current_dir(&self.path);
+                }
+                command
+            }
+            Some(sandbox) => {
+                // The binary is run from a throwaway directory
+                let work_dir = temp_dir().join("sandbox");
+                fs::create_dir_all(&work_dir)
+                    .map_err(Error::io("create the sandbox directory"))
+                    .map_err(RunFailure::Error)?;
+                let binary = fs::canonicalize(binary)
+                    .map_err(Error::io(format!("find the binary of {self}")))
+                    .map_err(RunFailure::Error)?;
+                let mut command = Command::new(binary);
+                sandbox
+                    .confine(&mut command, &work_dir)
+                    .map_err(Error::io("set up the sandbox"))
+                    .map_err(RunFailure::Error)?;
+                command
+            }
         };
-        let cmd = Command::new(temp_file())
-            .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
+        match self.mode {
+            Mode::Test | Mode::Cargo => {
+                command.arg("--show-output");
+            }
+            // Exercism marks every test but the first as ignored, for the end
+            // user to enable them one by one. They all have to pass eventually
+            Mode::Exercism => {
+                command.args(["--show-output", "--include-ignored"]);
+            }
+            // Set after confining the command, which clears the environment
+            Mode::Compile | Mode::Clippy | Mode::CompileFail => {
+                command.args(&self.args).envs(&self.env);
+            }
+        }
+        let stdin = self.stdin.as_ref().map(String::as_bytes);
+        let supervised = output_with_timeout(&mut command, stdin, self.timeout())
+            .map_err(Error::io(format!("run {self}")))
+            .map_err(RunFailure::Error)?;
+        let cmd = supervised.output;
 
         let output = ExerciseOutput {
             stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
             stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            diagnostics: Vec::new(),
         };
+        let violation = sandbox.and_then(|sandbox| {
+            sandbox.detect_violation(&cmd.status, supervised.cpu_time, &output.stderr)
+        });
 
-        if cmd.status.success() {
-            Ok(output)
+        if supervised.timed_out {
+            Err(RunFailure::TimedOut(output))
+        } else if let Some(violation) = violation {
+            Err(RunFailure::LimitExceeded(output, violation))
+        } else if !cmd.status.success() {
+            Err(RunFailure::Failed(output))
+        } else if let (Mode::Compile, Some(expected)) = (self.mode, &self.expected_stdout) {
+            if expected.matches(&output.stdout) {
+                Ok(output)
+            } else {
+                Err(RunFailure::WrongOutput(output))
+            }
         } else {
-            Err(output)
+            Ok(output)
         }
     }
 
-    pub fn state(&self) -> State {
-        let mut source_file =
-            File::open(&self.path).expect("We were unable to open the exercise file!");
-
-        let source = {
-            let mut s = String::new();
-            source_file
-                .read_to_string(&mut s)
-                .expect("We were unable to read the exercise file!");
-            s
+    /// Compile an exercise that's meant to be rejected by the compiler,
+    /// and check why it was rejected
+    pub fn compile_to_fail(&self) -> Rejection {
+        // Only the analysis matters, so there's no need to generate code
+        let args = [
+            &["--emit=metadata"],
+            &self.edition_args()[..],
+            RUSTC_JSON_ARGS,
+        ]
+        .concat();
+        let cmd = match cache::rustc(self, &args) {
+            Ok(cmd) => cmd,
+            Err(e) => return Rejection::Error(Error::spawn("rustc", e)),
         };
+        if cmd.success {
+            return Rejection::Accepted;
+        }
+
+        let diagnostics = diagnostic::parse(&String::from_utf8_lossy(&cmd.stderr));
+        let codes: Vec<String> = diagnostic::error_codes(&diagnostics)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let output = ExerciseOutput::diagnosed(diagnostics, &cmd.stderr);
+        match &self.expect_error {
+            Some(expected) if !codes.contains(expected) => Rejection::Unexpected(output, codes),
+            _ => Rejection::Expected(output),
+        }
+    }
+
+    /// The files making up the exercise: the exercise file itself,
+    /// or every file of a Cargo project
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        match self.mode {
+            Mode::Cargo | Mode::Exercism => project_files(&self.path),
+            _ => Ok(vec![self.path.clone()]),
+        }
+    }
+
+    /// Check whether the file at the given path is part of the exercise
+    pub fn owns(&self, path: &Path) -> bool {
+        path.ancestors()
+            .any(|ancestor| ancestor.ends_with(&self.path))
+            // Exercises outside the exercises directory, like Exercism
+            // crates, are found by where they really are
+            || (path.is_absolute()
+                && fs::canonicalize(&self.path).is_ok_and(|root| path.starts_with(root)))
+    }
+
+    /// The registry dependencies of a Cargo project exercise that aren't
+    /// vendored, by name. Those can't be resolved, since nothing is ever
+    /// downloaded
+    pub fn unvendored_dependencies(&self) -> Vec<String> {
+        match self.mode {
+            Mode::Cargo | Mode::Exercism => {
+                unvendored_dependencies(&self.path, Path::new(VENDOR_DIR))
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The edition the exercise is compiled with, unless it's rustc's default
+    pub fn edition(&self) -> Option<&str> {
+        match (&self.edition, self.mode) {
+            (Some(edition), _) => Some(edition),
+            (None, Mode::Clippy) => Some(CLIPPY_EDITION),
+            (None, _) => None,
+        }
+    }
+
+    // The arguments telling rustc the edition, if it isn't its default
+    fn edition_args(&self) -> Vec<&str> {
+        match self.edition() {
+            Some(edition) => vec!["--edition", edition],
+            None => Vec::new(),
+        }
+    }
 
+    /// The error the exercise has to be rejected with, for the end user
+    pub fn expected_error(&self) -> &str {
+        self.expect_error.as_deref().unwrap_or("an error")
+    }
+
+    /// The topic of the exercise: the one it's listed in, or else the
+    /// directory it lives in. Exercises at the top of the exercises
+    /// directory, like quizzes, only belong to the topic they're listed in.
+    pub fn topic(&self) -> Option<&str> {
+        if let Some(topic) = &self.topic {
+            return Some(topic);
+        }
+        let topic = self.path.parent()?.file_name()?.to_str()?;
+        (topic != "exercises").then_some(topic)
+    }
+
+    /// The path to the reference solution of the exercise
+    pub fn solution_path(&self) -> PathBuf {
+        if let Some(solution) = &self.solution {
+            return solution.clone();
+        }
+        match self.path.strip_prefix("exercises") {
+            Ok(relative) => Path::new("solutions").join(relative),
+            Err(_) => Path::new("solutions").join(&self.path),
+        }
+    }
+
+    /// How long the exercise may run before being killed
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+
+    /// Whether or not the `I AM NOT DONE` marker is still there, and where
+    pub fn state(&self) -> Result<State, Error> {
         let re = Regex::new(I_AM_DONE_REGEX).unwrap();
+        let files = self
+            .files()
+            .map_err(Error::io(format!("find the files of {self}")))?;
 
-        if !re.is_match(&source) {
-            return State::Done;
+        // The marker can be in any file of a Cargo project
+        let mut source = None;
+        for path in files {
+            let s =
+                fs::read_to_string(&path).map_err(Error::io(format!("read {}", path.display())))?;
+            if re.is_match(&s) {
+                source = Some(s);
+                break;
+            }
         }
+        let Some(source) = source else {
+            return Ok(State::Done);
+        };
 
         let matched_line_index = source
             .lines()
@@ -234,17 +912,7 @@ path = "{}.rs""#,
             })
             .collect();
 
-        State::Pending(context)
-    }
-
-    // Check that the exercise looks to be solved using self.state()
-    // This is not the best way to check since
-    // the user can just remove the "I AM NOT DONE" string from the file
-    // without actually having solved anything.
-    // The only other way to truly check this would to compile and run
-    // the exercise; which would be both costly and counterintuitive
-    pub fn looks_done(&self) -> bool {
-        self.state() == State::Done
+        Ok(State::Pending(context))
     }
 }
 
@@ -254,28 +922,148 @@ impl Display for Exercise {
     }
 }
 
+thread_local! {
+    // The worker thread evaluating exercises. Exercises evaluated outside of
+    // workers share the directory of the first one, as they never overlap
+    static WORKER: Cell<usize> = const { Cell::new(0) };
+}
+
+// Set the worker the current thread is, which picks its cargo target directory
+pub(crate) fn set_worker(worker: usize) {
+    WORKER.with(|w| w.set(worker));
+}
+
+// The cargo target directory of the current thread
+fn cargo_target_dir() -> PathBuf {
+    match WORKER.with(Cell::get) {
+        0 => PathBuf::from(CARGO_TARGET_DIR),
+        worker => PathBuf::from(format!("{CARGO_TARGET_DIR}-{worker}")),
+    }
+}
+
+// Prepare a cargo command working on the Cargo project in the given directory.
+// Dependencies are only ever resolved offline, from the vendored registry
+// if there is one.
+fn cargo_command(subcommand: &str, project: &Path) -> Command {
+    let mut command = Command::new("cargo");
+    command
+        .arg(subcommand)
+        .arg("--manifest-path")
+        .arg(project.join("Cargo.toml"))
+        .args(["--offline", "--quiet"])
+        .env("CARGO_TARGET_DIR", cargo_target_dir());
+    if let Ok(vendor) = fs::canonicalize(VENDOR_DIR) {
+        command
+            .arg("--config")
+            .arg("source.crates-io.replace-with='vendored-sources'")
+            .arg("--config")
+            .arg(vendored_sources(&vendor));
+    }
+    command
+}
+
+// The cargo setting for the directory of the vendored registry. The path
+// is written as a TOML string, so that backslashes and quotes survive
+fn vendored_sources(vendor: &Path) -> String {
+    let directory = toml::Value::String(vendor.to_string_lossy().into_owned());
+    format!("source.vendored-sources.directory={directory}")
+}
+
+// The registry dependencies of the Cargo project in the given directory that
+// no crate of the vendored registry provides. Dependencies on a path are
+// found without any registry
+fn unvendored_dependencies(project: &Path, vendor: &Path) -> Vec<String> {
+    let read_manifest = |path: &Path| {
+        let manifest = fs::read_to_string(path.join("Cargo.toml")).ok()?;
+        toml::from_str::<toml::Table>(&manifest).ok()
+    };
+    let Some(manifest) = read_manifest(project) else {
+        return Vec::new();
+    };
+    let vendored: Vec<String> = fs::read_dir(vendor)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| read_manifest(&entry.ok()?.path()))
+        .filter_map(|manifest| Some(manifest.get("package")?.get("name")?.as_str()?.to_string()))
+        .collect();
+
+    let mut missing = Vec::new();
+    for table in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        let Some(dependencies) = manifest.get(table).and_then(toml::Value::as_table) else {
+            continue;
+        };
+        for (name, dependency) in dependencies {
+            if dependency.get("path").is_some() {
+                continue;
+            }
+            // A dependency may be renamed, and then names its package
+            let package = dependency
+                .get("package")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(name);
+            if !vendored.iter().any(|crate_name| crate_name == package) {
+                missing.push(package.to_string());
+            }
+        }
+    }
+    missing
+}
+
+/// List every file of the Cargo project in the given directory, apart from
+/// what cargo generates, in a stable order
+pub fn project_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut directories = vec![root.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if path.file_name() != Some(OsStr::new("target")) {
+                    directories.push(path);
+                }
+            } else if path.file_name() != Some(OsStr::new("Cargo.lock")) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
 #[inline]
 fn clean() {
-    let _ignored = remove_file(temp_file());
+    let _ignored = remove_dir_all(temp_dir());
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::Path;
+
+    #[test]
+    fn test_workers_have_their_own_cargo_target_dir() {
+        let dirs = std::thread::spawn(|| {
+            let first = cargo_target_dir();
+            set_worker(2);
+            (first, cargo_target_dir())
+        })
+        .join()
+        .unwrap();
+        assert_eq!(dirs.0, Path::new(CARGO_TARGET_DIR));
+        assert_eq!(dirs.1, Path::new("./target/exercises-2"));
+    }
 
     #[test]
     fn test_clean() {
-        File::create(temp_file()).unwrap();
+        fs::create_dir_all(temp_dir()).unwrap();
+        fs::File::create(temp_dir().join("exercise")).unwrap();
         let exercise = Exercise {
             name: String::from("example"),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::from(""),
+            ..Default::default()
         };
         let compiled = exercise.compile().unwrap();
         drop(compiled);
-        assert!(!Path::new(&temp_file()).exists());
+        assert!(!temp_dir().exists());
     }
 
     #[test]
@@ -283,11 +1071,10 @@ mod test {
         let exercise = Exercise {
             name: "pending_exercise".into(),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
+            ..Default::default()
         };
 
-        let state = exercise.state();
+        let state = exercise.state().unwrap();
         let expected = vec![
             ContextLine {
                 line: "// fake_exercise".to_string(),
@@ -324,11 +1111,10 @@ mod test {
         let exercise = Exercise {
             name: "finished_exercise".into(),
             path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
+            ..Default::default()
         };
 
-        assert_eq!(exercise.state(), State::Done);
+        assert_eq!(exercise.state().unwrap(), State::Done);
     }
 
     #[test]
@@ -337,9 +1123,235 @@ mod test {
             name: "exercise_with_output".into(),
             path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
             mode: Mode::Test,
-            hint: String::new(),
+            ..Default::default()
         };
-        let out = exercise.compile().unwrap().run().unwrap();
+        let out = exercise.compile().unwrap().run(None).unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
     }
+
+    #[test]
+    fn test_hints_can_be_a_string_or_a_list() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[exercises]]
+            name = "one"
+            path = "one.rs"
+            mode = "compile"
+            hint = "Look closer"
+
+            [[exercises]]
+            name = "many"
+            path = "many.rs"
+            mode = "compile"
+            hint = ["Look closer", "Closer still"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(list.exercises[0].hints, ["Look closer"]);
+        assert_eq!(list.exercises[1].hints, ["Look closer", "Closer still"]);
+    }
+
+    #[test]
+    fn test_exercises_grouped_in_topics() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[topics]]
+            name = "variables"
+
+            [[topics.exercises]]
+            name = "variables1"
+            path = "exercises/variables/variables1.rs"
+            mode = "compile"
+            hint = ""
+
+            [[topics]]
+            name = "quiz1"
+            dir = "exercises"
+            readme = "exercises/QUIZ.md"
+
+            [[topics.exercises]]
+            name = "quiz1"
+            path = "exercises/quiz1.rs"
+            mode = "test"
+            hint = ""
+
+            [[exercises]]
+            name = "extra"
+            path = "exercises/extra.rs"
+            mode = "compile"
+            hint = ""
+            "#,
+        )
+        .unwrap();
+        let names: Vec<&str> = list.exercises.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["variables1", "quiz1", "extra"]);
+        assert_eq!(list.exercises[1].topic(), Some("quiz1"));
+        assert_eq!(list.exercises[2].topic(), None);
+
+        let variables = &list.topics[0];
+        assert_eq!(variables.dir, Path::new("exercises/variables"));
+        assert_eq!(variables.readme, Path::new("exercises/variables/README.md"));
+        assert!(variables.contains(&list.exercises[0]));
+        assert!(!variables.contains(&list.exercises[1]));
+        assert_eq!(list.topics[1].readme, Path::new("exercises/QUIZ.md"));
+    }
+
+    #[test]
+    fn test_gating_topics_are_required_by_following_topics() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[topics]]
+            name = "quiz1"
+            gates = true
+            exercises = []
+
+            [[topics]]
+            name = "quiz2"
+            exercises = []
+
+            [[topics]]
+            name = "structs"
+            requires = ["variables"]
+
+            [[topics.exercises]]
+            name = "structs1"
+            path = "exercises/structs/structs1.rs"
+            mode = "compile"
+            hint = ""
+            requires = ["structs2"]
+            "#,
+        )
+        .unwrap();
+        assert!(list.topics[0].requires.is_empty());
+        // Only the topics that say so gate the others, whatever their name
+        assert_eq!(list.topics[1].requires, ["quiz1"]);
+        assert_eq!(list.topics[2].requires, ["variables", "quiz1"]);
+        assert_eq!(
+            list.exercises[0].requires,
+            ["variables", "quiz1", "structs2"]
+        );
+    }
+
+    #[test]
+    fn test_compiling_compile_fail_exercises_checks_them() {
+        let exercise = Exercise {
+            name: "rejected".into(),
+            path: PathBuf::from("tests/fixture/compile_fail/exercises/rejected.rs"),
+            mode: Mode::CompileFail,
+            expect_error: Some("E0382".into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            exercise.compile(),
+            Err(CompileError::MeantToBeRejected(Rejection::Expected(_)))
+        ));
+    }
+
+    #[test]
+    fn test_editions() {
+        let compiled = Exercise::default();
+        assert_eq!(compiled.edition(), None);
+        assert!(compiled.edition_args().is_empty());
+
+        let clippy = Exercise {
+            mode: Mode::Clippy,
+            ..Default::default()
+        };
+        assert_eq!(clippy.edition(), Some("2018"));
+        assert_eq!(clippy.edition_args(), ["--edition", "2018"]);
+
+        let modern = Exercise {
+            edition: Some("2021".into()),
+            ..clippy
+        };
+        assert_eq!(modern.edition(), Some("2021"));
+    }
+
+    #[test]
+    fn test_expected_output() {
+        let exact = ExpectedOutput::Exact("Hello\nWorld\n".into());
+        assert!(exact.matches("Hello\r\nWorld"));
+        assert!(!exact.matches("Hello\nworld\n"));
+
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[exercises]]
+            name = "sum"
+            path = "sum.rs"
+            mode = "compile"
+            hint = ""
+            expected_stdout = { regex = '^\d+\n$' }
+            "#,
+        )
+        .unwrap();
+        let pattern = list.exercises[0].expected_stdout.as_ref().unwrap();
+        assert!(pattern.matches("42\n"));
+        assert!(!pattern.matches("forty-two\n"));
+    }
+
+    #[test]
+    fn test_cargo_project() {
+        let exercise = Exercise {
+            name: "project".into(),
+            path: PathBuf::from("tests/fixture/cargo/exercises/project"),
+            mode: Mode::Cargo,
+            ..Default::default()
+        };
+        let files = exercise.files().unwrap();
+        let vegetables = exercise.path.join("src/garden/vegetables.rs");
+        assert_eq!(
+            files,
+            [
+                exercise.path.join("Cargo.toml"),
+                vegetables.clone(),
+                exercise.path.join("src/garden.rs"),
+                exercise.path.join("src/lib.rs"),
+            ]
+        );
+        assert!(exercise.owns(&vegetables.canonicalize().unwrap()));
+        // The marker is in a submodule
+        assert!(matches!(exercise.state(), Ok(State::Pending(_))));
+    }
+
+    #[test]
+    fn test_vendored_sources_are_written_in_toml() {
+        let vendor = Path::new(r#"C:\Users\me\"rustlings"\vendor"#);
+        let setting = vendored_sources(vendor);
+        let config: toml::Table = toml::from_str(&setting).unwrap();
+        let directory = &config["source"]["vendored-sources"]["directory"];
+        assert_eq!(directory.as_str(), vendor.to_str());
+    }
+
+    #[test]
+    fn test_unvendored_dependencies() {
+        let project = Path::new("tests/fixture/cargo/exercises/project");
+        let vendor = Path::new("tests/fixture/cargo/vendor");
+        assert!(unvendored_dependencies(project, vendor).is_empty());
+        assert_eq!(
+            unvendored_dependencies(project, Path::new("does_not_exist")),
+            ["greeter"]
+        );
+    }
+
+    #[test]
+    fn test_exercism_crates() {
+        // Deserializing info.toml doesn't look for them
+        let info = r#"exercism = "exercism""#;
+        let list: ExerciseList = toml::from_str(info).unwrap();
+        assert!(list.topics.is_empty() && list.exercises.is_empty());
+
+        let root = Path::new("tests/fixture/exercism");
+        let list = ExerciseList::load(info, root).unwrap();
+        assert_eq!(list.topics[0].name, EXERCISM_TOPIC);
+        assert_eq!(list.topics[0].exercises, ["greeting"]);
+        let exercise = &list.exercises[0];
+        assert!(matches!(exercise.mode, Mode::Exercism));
+        assert_eq!(exercise.topic(), Some(EXERCISM_TOPIC));
+        assert!(exercise.hints[0].starts_with("# Greeting"));
+        assert_eq!(exercise.path, Path::new("exercism/greeting"));
+
+        // A missing directory doesn't have any crate
+        let list = ExerciseList::load(r#"exercism = "does_not_exist""#, root).unwrap();
+        assert!(list.topics.is_empty() && list.exercises.is_empty());
+    }
 }
@@ -0,0 +1,110 @@
+use crate::diagnostic::Diagnostic;
+use crate::exercise::{Exercise, Mode};
+use crate::progress::Verification;
+use crate::sandbox::Violation;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The output format of the commands
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Format {
+    /// Colored prose meant to be read by humans
+    #[default]
+    Text,
+    /// One JSON object per line, meant to be consumed by other tools
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{s}', expected 'text' or 'json'")),
+        }
+    }
+}
+
+/// What happened when an exercise was compiled and run
+#[derive(Serialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The exercise didn't compile
+    CompileFailure,
+    /// The exercise compiled, but it's meant to be rejected by the compiler
+    NotRejected,
+    /// The exercise was rejected by the compiler, but not with the expected error
+    WrongError,
+    /// The exercise compiled, but running it failed
+    RunFailure,
+    /// The exercise ran successfully, but didn't print what it was expected to
+    WrongOutput,
+    /// The exercise compiled, but was killed for running too long
+    Timeout,
+    /// The exercise compiled, but ran into one of the limits of the sandbox
+    LimitExceeded,
+    /// The exercise compiled and ran successfully
+    Success,
+    /// The exercise couldn't be compiled or run at all
+    Error,
+}
+
+/// The machine-readable record of an exercise.
+/// Fields that don't apply to the command that emits the record are omitted
+#[derive(Serialize, Debug)]
+pub struct ExerciseRecord<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub mode: Mode,
+    pub state: Verification,
+    /// Only set for exercises that can't be worked on yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    /// Whether the exercise compiles as it is now, when a cached build tells
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compiles: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violation: Option<Violation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    /// Every diagnostic of the compiler, not only the ones shown on stderr
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<&'a [Diagnostic]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u128>,
+}
+
+/// Print a record as a single line of JSON on stdout
+pub fn emit(record: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+    line.push('\n');
+    io::stdout().lock().write_all(line.as_bytes())
+}
+
+impl<'a> ExerciseRecord<'a> {
+    /// A record holding only the static information about the exercise
+    pub fn new(exercise: &'a Exercise, state: Verification) -> Self {
+        ExerciseRecord {
+            name: &exercise.name,
+            path: &exercise.path,
+            mode: exercise.mode,
+            state,
+            locked: None,
+            compiles: None,
+            status: None,
+            violation: None,
+            stdout: None,
+            stderr: None,
+            diagnostics: None,
+            duration_ms: None,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::exercise::{Exercise, Mode};
+use crate::progress::Progress;
+use crate::readme;
+use console::style;
+use std::io;
+
+/// Reveal the next hint of the exercise, or all of them at once,
+/// and remember how far the end user got.
+/// Returns how many hints have been revealed so far.
+pub fn reveal(exercise: &Exercise, progress: &mut Progress, all: bool) -> io::Result<usize> {
+    let total = exercise.hints.len();
+    // There's nothing to remember about a single hint
+    if total <= 1 {
+        return Ok(total);
+    }
+
+    let revealed = if all {
+        total
+    } else {
+        (progress.hints_revealed(exercise) + 1).min(total)
+    };
+    progress.reveal_hints(exercise, revealed);
+    progress.save()?;
+    Ok(revealed)
+}
+
+/// Render the first `revealed` hints of the exercise.
+/// The hint of an Exercism crate is its README, which is rendered as such
+pub fn render(exercise: &Exercise, revealed: usize) -> String {
+    if let Mode::Exercism = exercise.mode {
+        return readme::render(&exercise.hints.concat());
+    }
+    let total = exercise.hints.len();
+    if total <= 1 {
+        return exercise.hints.concat();
+    }
+
+    exercise
+        .hints
+        .iter()
+        .take(revealed)
+        .enumerate()
+        .map(|(i, hint)| {
+            format!(
+                "{} {hint}",
+                style(format!("Hint {}/{total}:", i + 1)).bold()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn exercise(hints: &[&str]) -> Exercise {
+        Exercise {
+            name: "hinted".into(),
+            path: PathBuf::from("hinted.rs"),
+            hints: hints.iter().map(|hint| hint.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_single_hint_is_shown_as_is() {
+        assert_eq!(render(&exercise(&["Look closer"]), 1), "Look closer");
+    }
+
+    #[test]
+    fn test_revealed_hints_are_numbered() {
+        let exercise = exercise(&["Nudge", "Detail", "Almost the answer"]);
+        let rendered = console::strip_ansi_codes(&render(&exercise, 2)).to_string();
+        assert_eq!(rendered, "Hint 1/3: Nudge\n\nHint 2/3: Detail");
+    }
+}
@@ -0,0 +1,43 @@
+//! The exercises of Rustlings, and everything needed to check them.
+//!
+//! The `rustlings` command line is built on this library, which can also be
+//! used by other tools. Nothing here prints to the terminal: every function
+//! returns what happened, and showing it is up to the caller.
+//!
+//! ```no_run
+//! use rustlings::exercise::ExerciseList;
+//! use rustlings::outcome::{evaluate, Outcome};
+//! use std::path::Path;
+//!
+//! let info = std::fs::read_to_string("info.toml").unwrap();
+//! let list = ExerciseList::load(&info, Path::new("")).unwrap();
+//! for exercise in &list.exercises {
+//!     let (outcome, duration) = evaluate(exercise, None, || {});
+//!     let passed = matches!(outcome, Outcome::Success(_));
+//!     println!("{exercise}: {passed} in {duration:?}");
+//! }
+//! ```
+//!
+//! - [`exercise`] loads the list of exercises from `info.toml`, and compiles,
+//!   runs and tests a single exercise.
+//! - [`outcome`] evaluates exercises, one or several at once, into an
+//!   [`Outcome`](outcome::Outcome).
+//! - [`progress`] keeps track of the exercises that are done.
+//! - [`error`] is everything that can go wrong, along with its exit code.
+
+mod cache;
+pub mod diagnostic;
+pub mod diff;
+pub mod error;
+pub mod exercise;
+pub mod format;
+pub mod hint;
+pub mod lint;
+pub mod outcome;
+pub mod pristine;
+pub mod progress;
+pub mod readme;
+pub mod sandbox;
+pub mod scaffold;
+pub mod solution;
+mod supervisor;
@@ -0,0 +1,424 @@
+use crate::exercise::{project_files, ExerciseList, Mode, State};
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INFO_FILE: &str = "info.toml";
+
+/// How bad an issue is. Errors keep rustlings from starting at all,
+/// while warnings are only shown by `lint-info`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Something wrong with info.toml or the exercises it lists
+#[derive(PartialEq, Eq, Debug)]
+pub struct Issue {
+    pub severity: Severity,
+    /// The file the issue is in, and the line if it's known
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, ": {severity}: {}", self.message)
+    }
+}
+
+// The lines of info.toml where every exercise and topic entry is named,
+// in the order they appear
+struct Positions {
+    exercises: Vec<(String, usize)>,
+    topics: Vec<(String, usize)>,
+}
+
+impl Positions {
+    fn new(info: &str) -> Self {
+        let name = Regex::new(r#"^\s*name\s*=\s*"([^"]*)""#).unwrap();
+        let mut positions = Positions {
+            exercises: Vec::new(),
+            topics: Vec::new(),
+        };
+        let mut table = "";
+        for (index, line) in info.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                table = trimmed;
+            } else if let Some(captures) = name.captures(line) {
+                let entry = (captures[1].to_string(), index + 1);
+                match table {
+                    "[[topics]]" => positions.topics.push(entry),
+                    "[[topics.exercises]]" | "[[exercises]]" => positions.exercises.push(entry),
+                    _ => {}
+                }
+            }
+        }
+        positions
+    }
+
+    // The line every exercise of the list is named on.
+    // Exercises with the same name are told apart by their order
+    fn exercise_lines(&self, list: &ExerciseList) -> Vec<Option<usize>> {
+        let mut seen = BTreeMap::new();
+        list.exercises
+            .iter()
+            .map(|exercise| {
+                let nth = seen.entry(exercise.name.as_str()).or_insert(0);
+                *nth += 1;
+                lines_of(&self.exercises, &exercise.name)
+                    .get(*nth - 1)
+                    .copied()
+            })
+            .collect()
+    }
+}
+
+fn lines_of(entries: &[(String, usize)], name: &str) -> Vec<usize> {
+    entries
+        .iter()
+        .filter(|(entry, _)| entry == name)
+        .map(|(_, line)| *line)
+        .collect()
+}
+
+fn in_info(line: Option<usize>, message: String) -> Issue {
+    Issue {
+        severity: Severity::Error,
+        file: PathBuf::from(INFO_FILE),
+        line,
+        message,
+    }
+}
+
+fn warning(file: &Path, message: String) -> Issue {
+    Issue {
+        severity: Severity::Warning,
+        file: file.to_path_buf(),
+        line: None,
+        message,
+    }
+}
+
+/// The problems that keep rustlings from working: exercises and topics named
+/// twice, exercises that don't exist, and requirements that don't exist
+pub fn check(info: &str, list: &ExerciseList) -> Vec<Issue> {
+    let positions = Positions::new(info);
+    let mut issues = Vec::new();
+
+    let lines = positions.exercise_lines(list);
+    let mut names = HashSet::new();
+    for (exercise, line) in list.exercises.iter().zip(&lines) {
+        if !names.insert(exercise.name.as_str()) {
+            issues.push(in_info(
+                *line,
+                format!("there already is an exercise named '{}'", exercise.name),
+            ));
+        }
+    }
+    let exists = |name: &String| {
+        list.exercises.iter().any(|e| &e.name == name)
+            || list.topics.iter().any(|t| &t.name == name)
+    };
+    let mut topics = HashSet::new();
+    for (index, topic) in list.topics.iter().enumerate() {
+        let nth = list.topics[..index]
+            .iter()
+            .filter(|t| t.name == topic.name)
+            .count();
+        let line = lines_of(&positions.topics, &topic.name).get(nth).copied();
+        if !topics.insert(topic.name.as_str()) {
+            issues.push(in_info(
+                line,
+                format!("there already is a topic named '{}'", topic.name),
+            ));
+        }
+        for requirement in topic.requires.iter().filter(|r| !exists(r)) {
+            issues.push(in_info(
+                line,
+                format!(
+                    "topic {} requires '{requirement}', which is neither an exercise nor a topic",
+                    topic.name
+                ),
+            ));
+        }
+    }
+
+    for (exercise, line) in list.exercises.iter().zip(&lines) {
+        let line = *line;
+        if !exercise.path.exists() {
+            issues.push(in_info(
+                line,
+                format!(
+                    "the file of {} doesn't exist: {}",
+                    exercise.name,
+                    exercise.path.display()
+                ),
+            ));
+        }
+        // The requirements of its topic were checked with the topic
+        let topic = list
+            .topics
+            .iter()
+            .find(|t| exercise.topic() == Some(t.name.as_str()));
+        let own = exercise
+            .requires
+            .iter()
+            .filter(|r| !topic.is_some_and(|t| t.requires.contains(r)));
+        for requirement in own.filter(|r| !exists(r)) {
+            issues.push(in_info(
+                line,
+                format!(
+                    "{} requires '{requirement}', which is neither an exercise nor a topic",
+                    exercise.name
+                ),
+            ));
+        }
+    }
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// Every problem with info.toml and the exercises tree: those found by
+/// `check`, along with exercises that look unfinished as authored, Cargo
+/// projects depending on crates that aren't vendored, topics without a
+/// README, gating topics that gate nothing, and files under `dir` that no
+/// exercise refers to
+pub fn lint(info: &str, list: &ExerciseList, dir: &Path) -> Vec<Issue> {
+    let positions = Positions::new(info);
+    let lines = positions.exercise_lines(list);
+    let mut issues = check(info, list);
+
+    for topic in &list.topics {
+        if !topic.readme.exists() {
+            let line = lines_of(&positions.topics, &topic.name).first().copied();
+            issues.push(Issue {
+                severity: Severity::Warning,
+                ..in_info(
+                    line,
+                    format!(
+                        "the README of topic {} doesn't exist: {}",
+                        topic.name,
+                        topic.readme.display()
+                    ),
+                )
+            });
+        }
+    }
+
+    for (index, topic) in list.topics.iter().enumerate() {
+        if !topic.gates {
+            continue;
+        }
+        let line = lines_of(&positions.topics, &topic.name).first().copied();
+        let gated = list.topics[index + 1..]
+            .iter()
+            .any(|t| t.requires.contains(&topic.name));
+        let problem = if topic.exercises.is_empty() {
+            Some("has no exercises, so it's always done")
+        } else if !gated {
+            Some("is the last topic")
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                ..in_info(
+                    line,
+                    format!("topic {} gates nothing: it {problem}", topic.name),
+                )
+            });
+        }
+    }
+
+    for (exercise, line) in list.exercises.iter().zip(lines) {
+        if exercise.hints.iter().all(|hint| hint.trim().is_empty()) {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                ..in_info(line, format!("{} has no hint", exercise.name))
+            });
+        }
+        // Missing files were reported already
+        if !exercise.path.exists() {
+            continue;
+        }
+        // Exercism crates are done once their tests pass
+        let marked = !matches!(exercise.mode, Mode::Exercism);
+        if marked && matches!(exercise.state(), Ok(State::Done)) {
+            issues.push(warning(
+                &exercise.path,
+                format!("there is no `I AM NOT DONE` marker in {}", exercise.name),
+            ));
+        }
+        for dependency in exercise.unvendored_dependencies() {
+            issues.push(warning(
+                &exercise.path.join("Cargo.toml"),
+                format!(
+                    "{} depends on '{dependency}', which isn't vendored, so it can't be built offline",
+                    exercise.name
+                ),
+            ));
+        }
+        if let Mode::Test = exercise.mode {
+            let source = fs::read_to_string(&exercise.path).unwrap_or_default();
+            if !source.contains("#[test]") {
+                issues.push(warning(
+                    &exercise.path,
+                    format!("{} is a test exercise without any #[test]", exercise.name),
+                ));
+            }
+        }
+    }
+
+    if let Ok(files) = project_files(dir) {
+        for file in files {
+            let unreferenced = file.extension().is_some_and(|e| e == "rs")
+                && !list.exercises.iter().any(|e| e.owns(&file));
+            if unreferenced {
+                issues.push(warning(
+                    &file,
+                    "no exercise in info.toml refers to this file".into(),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INFO: &str = r#"[[topics]]
+name = "basics"
+
+[[topics.exercises]]
+name = "basics1"
+path = "tests/fixture/state/pending_exercise.rs"
+mode = "compile"
+hint = ""
+
+[[topics.exercises]]
+name = "basics1"
+path = "tests/fixture/state/does_not_exist.rs"
+mode = "test"
+hint = "Look closer"
+requires = ["advanced"]
+"#;
+
+    #[test]
+    fn test_check_reports_lines() {
+        let list: ExerciseList = toml::from_str(INFO).unwrap();
+        let messages: Vec<String> = check(INFO, &list).iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "info.toml:11: error: there already is an exercise named 'basics1'",
+                "info.toml:11: error: the file of basics1 doesn't exist: tests/fixture/state/does_not_exist.rs",
+                "info.toml:11: error: basics1 requires 'advanced', which is neither an exercise nor a topic",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_reports_topic_requirements_once() {
+        let info = r#"[[topics]]
+name = "empty"
+requires = ["nothing"]
+exercises = []
+
+[[topics]]
+name = "basics"
+requires = ["advanced"]
+
+[[topics.exercises]]
+name = "basics1"
+path = "tests/fixture/state/pending_exercise.rs"
+mode = "compile"
+hint = ""
+
+[[topics.exercises]]
+name = "basics2"
+path = "tests/fixture/state/finished_exercise.rs"
+mode = "compile"
+hint = ""
+requires = ["basics3"]
+"#;
+        let list: ExerciseList = toml::from_str(info).unwrap();
+        let messages: Vec<String> = check(info, &list).iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "info.toml:2: error: topic empty requires 'nothing', which is neither an exercise nor a topic",
+                "info.toml:7: error: topic basics requires 'advanced', which is neither an exercise nor a topic",
+                "info.toml:17: error: basics2 requires 'basics3', which is neither an exercise nor a topic",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_reports_warnings() {
+        let list: ExerciseList = toml::from_str(INFO).unwrap();
+        let warnings: Vec<String> = lint(INFO, &list, Path::new("tests/fixture/state/solutions"))
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "info.toml:2: warning: the README of topic basics doesn't exist: exercises/basics/README.md",
+                "info.toml:5: warning: basics1 has no hint",
+                "tests/fixture/state/solutions/pending_exercise.rs: warning: no exercise in info.toml refers to this file",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_reports_gating_topics_that_gate_nothing() {
+        let info = r#"[[topics]]
+name = "quiz1"
+readme = "tests/fixture/state/README.md"
+gates = true
+exercises = []
+
+[[topics]]
+name = "quiz2"
+readme = "tests/fixture/state/README.md"
+gates = true
+
+[[topics.exercises]]
+name = "quiz2"
+path = "tests/fixture/state/finished_exercise.rs"
+mode = "compile"
+hint = "Read it again"
+"#;
+        let list: ExerciseList = toml::from_str(info).unwrap();
+        let warnings: Vec<String> = lint(info, &list, Path::new("tests/fixture/state/solutions"))
+            .iter()
+            .filter(|i| i.message.contains("gates"))
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "info.toml:2: warning: topic quiz1 gates nothing: it has no exercises, so it's always done",
+                "info.toml:8: warning: topic quiz2 gates nothing: it is the last topic",
+            ]
+        );
+    }
+}
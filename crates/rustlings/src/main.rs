@@ -1,27 +1,30 @@
-use crate::exercise::{Exercise, ExerciseList};
 use crate::run::run;
-use crate::verify::verify;
+use crate::ui::confirm;
+use crate::verify::{check, verify, VerifyOptions};
+use crate::watch::{watch, WatchStatus};
 use argh::FromArgs;
 use console::Emoji;
-use notify::DebouncedEvent;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use std::ffi::OsStr;
+use rustlings::error::Error;
+use rustlings::exercise::{Exercise, ExerciseList, Topic};
+use rustlings::format::{self, ExerciseRecord, Format};
+use rustlings::progress::{self, Progress, PROGRESS_FILE_PATH};
+use rustlings::sandbox::Sandbox;
+use rustlings::scaffold::Template;
+use rustlings::{diff, hint, lint, pristine, readme, scaffold, solution};
 use std::fs;
-use std::io::{self, prelude::*};
+use std::io::prelude::*;
 use std::path::Path;
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::{channel, RecvTimeoutError};
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
 
 #[macro_use]
 mod ui;
 
-mod exercise;
+mod dev;
 mod run;
+mod shell;
+mod tui;
 mod verify;
+mod watch;
 
 // In sync with crate version
 const VERSION: &str = "4.6.0";
@@ -35,6 +38,13 @@ struct Args {
     /// show the executable version
     #[argh(switch, short = 'v')]
     version: bool,
+    /// the output format of `list`, `verify` and `run`: text or json
+    #[argh(option, default = "Format::Text")]
+    format: Format,
+    /// run exercises with resource limits, a clean environment and a
+    /// throwaway working directory (Linux only)
+    #[argh(switch)]
+    sandbox: bool,
     #[argh(subcommand)]
     nested: Option<Subcommands>,
 }
@@ -47,17 +57,38 @@ enum Subcommands {
     Run(RunArgs),
     Hint(HintArgs),
     List(ListArgs),
+    Reset(ResetArgs),
+    Diff(DiffArgs),
+    Solution(SolutionArgs),
+    Readme(ReadmeArgs),
+    New(NewArgs),
+    LintInfo(LintInfoArgs),
+    Dev(DevArgs),
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "verify")]
 /// Verifies all exercises according to the recommended order
-struct VerifyArgs {}
+struct VerifyArgs {
+    #[argh(option, short = 'j', default = "1")]
+    /// the number of exercises to compile and run concurrently
+    jobs: usize,
+    #[argh(switch, short = 'k')]
+    /// keep verifying the remaining exercises after a failure
+    keep_going: bool,
+    #[argh(option, short = 't')]
+    /// verify only the exercises of the given topic
+    topic: Option<String>,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "watch")]
 /// Reruns `verify` when files were edited
-struct WatchArgs {}
+struct WatchArgs {
+    #[argh(switch)]
+    /// show a full-screen interface instead of the plain output
+    tui: bool,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "run")]
@@ -75,8 +106,89 @@ struct HintArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    #[argh(switch, short = 'a')]
+    /// reveal every hint at once
+    all: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "reset")]
+/// Restores exercises to their original state
+struct ResetArgs {
+    #[argh(positional)]
+    /// the name of the exercise, or of a topic to reset all of its exercises
+    name: Option<String>,
+    #[argh(switch, short = 'a')]
+    /// reset every exercise
+    all: bool,
+    #[argh(switch, short = 'y')]
+    /// don't ask for confirmation before resetting several exercises
+    yes: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "diff")]
+/// Shows the changes made to an exercise since it was shipped
+struct DiffArgs {
+    #[argh(positional)]
+    /// the name of the exercise
+    name: String,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "solution")]
+/// Shows the reference solution of an exercise you've completed
+struct SolutionArgs {
+    #[argh(positional)]
+    /// the name of the exercise
+    name: String,
+    #[argh(switch, short = 'c')]
+    /// show your code and the solution side by side
+    compare: bool,
+    #[argh(switch)]
+    /// show the solution even though the exercise isn't done yet
+    force: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "new")]
+/// Creates a new exercise at the end of a topic
+struct NewArgs {
+    #[argh(positional)]
+    /// the topic of the exercise, which is created if it doesn't exist yet
+    topic: String,
+    #[argh(positional)]
+    /// the name of the exercise
+    name: String,
+    #[argh(option, short = 'm', default = "Template::Compile")]
+    /// how the exercise is checked: compile, test or clippy
+    mode: Template,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "lint-info")]
+/// Checks info.toml and the exercises it lists for mistakes
+struct LintInfoArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "dev")]
+/// Commands for exercise authors
+struct DevArgs {
+    #[argh(subcommand)]
+    nested: DevSubcommands,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+enum DevSubcommands {
+    Check(DevCheckArgs),
 }
 
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "check")]
+/// Checks that every reference solution passes and every exercise fails
+struct DevCheckArgs {}
+
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "list")]
 /// Lists the exercises available in Rustlings
@@ -97,14 +209,54 @@ struct ListArgs {
     #[argh(switch, short = 's')]
     /// display only exercises that have been solved
     solved: bool,
+    #[argh(option, short = 't')]
+    /// display only the exercises of the given topic
+    topic: Option<String>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "readme")]
+/// Shows the introduction to a topic
+struct ReadmeArgs {
+    #[argh(positional)]
+    /// the name of the topic
+    topic: String,
 }
 
 fn main() {
-    let args: Args = argh::from_env();
+    if let Err(e) = parse_args().and_then(run_command) {
+        if !e.is_reported() {
+            ui::message(&e.to_string());
+        }
+        std::process::exit(e.exit_code());
+    }
+}
 
+// Like `argh::from_env`, but a command line that doesn't parse is a usage error
+fn parse_args() -> Result<Args, Error> {
+    let strings: Vec<String> = std::env::args().collect();
+    let command = Path::new(&strings[0])
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(&strings[0]);
+    let rest: Vec<&str> = strings[1..].iter().map(String::as_str).collect();
+    Args::from_args(&[command], &rest).map_err(|early_exit| match early_exit.status {
+        Ok(()) => {
+            println!("{}", early_exit.output);
+            std::process::exit(0);
+        }
+        Err(()) => Error::Usage(format!(
+            "{}\nRun {command} --help for more information.",
+            early_exit.output.trim_end()
+        )),
+    })
+}
+
+fn run_command(args: Args) -> Result<(), Error> {
+    ui::set_format(args.format);
     if args.version {
         println!("v{VERSION}");
-        std::process::exit(0);
+        return Ok(());
     }
 
     if args.nested.is_none() {
@@ -120,26 +272,57 @@ fn main() {
     }
 
     if !Path::new("info.toml").exists() {
-        println!(
-            "{} must be run from the rustlings directory",
+        return Err(Error::Config(format!(
+            "{} must be run from the rustlings directory\nTry `cd rustlings/`!",
             std::env::current_exe().unwrap().to_str().unwrap()
-        );
-        println!("Try `cd rustlings/`!");
-        std::process::exit(1);
+        )));
     }
 
     if !rustc_exists() {
-        println!("We cannot find `rustc`.");
-        println!("Try running `rustc --version` to diagnose your problem.");
-        println!("For instructions on how to install Rust, check the README.");
-        std::process::exit(1);
+        return Err(Error::ToolchainMissing("rustc"));
+    }
+
+    let toml_str = &fs::read_to_string("info.toml").map_err(Error::io("read info.toml"))?;
+    let list = ExerciseList::load(toml_str, Path::new(""))
+        .map_err(|e| Error::Config(format!("info.toml is invalid: {e}")))?;
+    // `lint-info` reports these along with everything else
+    if !matches!(args.nested, Some(Subcommands::LintInfo(_))) {
+        let issues = lint::check(toml_str, &list);
+        if !issues.is_empty() {
+            for issue in &issues {
+                ui::message(&issue.to_string());
+            }
+            ui::message("");
+            return Err(Error::Config(
+                "Please fix info.toml before running rustlings.".into(),
+            ));
+        }
+    }
+    let ExerciseList {
+        topics,
+        exercises,
+        exercism,
+    } = list;
+    let mut progress = Progress::load(PROGRESS_FILE_PATH).map_err(|e| {
+        Error::Config(format!(
+            "Failed to read the progress file {PROGRESS_FILE_PATH}: {e}\nRemove it to start over with an empty progress."
+        ))
+    })?;
+    if args.sandbox && !cfg!(target_os = "linux") {
+        return Err(Error::Usage(
+            "The sandbox is only available on Linux.".into(),
+        ));
     }
 
-    let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
     let verbose = args.nocapture;
+    let format = args.format;
+    let options = VerifyOptions {
+        format,
+        sandbox: args.sandbox.then(Sandbox::default),
+        ..VerifyOptions::new(verbose)
+    };
 
-    let command = args.nested.unwrap_or_else(|| {
+    let Some(command) = args.nested else {
         let text = "Thanks for installing Rustlings!
 
 Is this your first time? Don't worry, Rustlings was made for beginners! We are
@@ -167,254 +350,434 @@ Got all that? Great! To get started, run `rustlings watch` in order to get the f
 exercise. Make sure to have your editor open!
 ";
         println!("{text}");
-        std::process::exit(0);
-    });
+        return Ok(());
+    };
     match command {
         Subcommands::List(subargs) => {
-            if !subargs.paths && !subargs.names {
+            if !subargs.paths && !subargs.names && format == Format::Text {
                 println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
             }
-            let mut exercises_done: u16 = 0;
             let filters = subargs.filter.clone().unwrap_or_default().to_lowercase();
-            exercises.iter().for_each(|e| {
+            let topic = subargs
+                .topic
+                .as_deref()
+                .map(|name| find_topic(name, &topics))
+                .transpose()?;
+            let in_topic = |e: &Exercise| topic.is_none_or(|topic| topic.contains(e));
+            let done_exercises = progress.done(&exercises);
+            for e in exercises.iter().filter(|e| in_topic(e)) {
                 let fname = format!("{}", e.path.display());
                 let filter_cond = filters
                     .split(',')
                     .filter(|f| !f.trim().is_empty())
                     .any(|f| e.name.contains(f) || fname.contains(f));
-                let status = if e.looks_done() {
-                    exercises_done += 1;
-                    "Done"
-                } else {
-                    "Pending"
+                let done = done_exercises.contains(e.name.as_str());
+                // Earlier builds tell the exercises that don't compile apart,
+                // without compiling anything
+                let compiles = e.compiles();
+                let status = match compiles {
+                    Some(false)
+                        if !done && progress::is_unlocked(e, &exercises, &done_exercises) =>
+                    {
+                        "Broken"
+                    }
+                    _ => progress.label(e, &exercises, &done_exercises),
                 };
                 let solve_cond = {
-                    (e.looks_done() && subargs.solved)
-                        || (!e.looks_done() && subargs.unsolved)
+                    (done && subargs.solved)
+                        || (!done && subargs.unsolved)
                         || (!subargs.solved && !subargs.unsolved)
                 };
                 if solve_cond && (filter_cond || subargs.filter.is_none()) {
-                    let line = if subargs.paths {
-                        format!("{fname}\n")
-                    } else if subargs.names {
-                        format!("{}\n", e.name)
+                    let written = if format == Format::Json {
+                        format::emit(&ExerciseRecord {
+                            locked: (!done
+                                && !progress::is_unlocked(e, &exercises, &done_exercises))
+                            .then_some(true),
+                            compiles,
+                            ..ExerciseRecord::new(e, progress.verification(e))
+                        })
                     } else {
-                        format!("{:<17}\t{:<46}\t{:<7}\n", e.name, fname, status)
+                        let line = if subargs.paths {
+                            format!("{fname}\n")
+                        } else if subargs.names {
+                            format!("{}\n", e.name)
+                        } else {
+                            format!("{:<17}\t{:<46}\t{:<7}\n", e.name, fname, status)
+                        };
+                        std::io::stdout().lock().write_all(line.as_bytes())
                     };
                     // Somehow using println! leads to the binary panicking
                     // when its output is piped.
                     // So, we're handling a Broken Pipe error and exiting with 0 anyway
-                    let stdout = std::io::stdout();
-                    {
-                        let mut handle = stdout.lock();
-                        handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
-                            match e.kind() {
-                                std::io::ErrorKind::BrokenPipe => std::process::exit(0),
-                                _ => std::process::exit(1),
-                            };
-                        });
+                    if let Err(e) = written {
+                        return match e.kind() {
+                            std::io::ErrorKind::BrokenPipe => Ok(()),
+                            _ => Err(Error::Io("write the list".into(), e)),
+                        };
                     }
                 }
-            });
-            let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
-            println!(
-                "Progress: You completed {} / {} exercises ({:.2} %).",
-                exercises_done,
-                exercises.len(),
-                percentage_progress
-            );
-            std::process::exit(0);
+            }
+            if format == Format::Json {
+                return Ok(());
+            }
+            if !subargs.paths && !subargs.names && !topics.is_empty() {
+                println!();
+                println!("{:<24}\t{:<40}\tProgress", "Topic", "Path");
+                for t in topics
+                    .iter()
+                    .filter(|t| topic.is_none_or(|topic| topic.name == t.name))
+                {
+                    let done = exercises
+                        .iter()
+                        .filter(|e| t.contains(e) && done_exercises.contains(e.name.as_str()))
+                        .count();
+                    let total = t.exercises.len();
+                    let missing =
+                        progress::missing_requirements(&t.requires, &exercises, &done_exercises);
+                    let locked = if missing.is_empty() {
+                        String::new()
+                    } else {
+                        format!("\tLocked, requires {}", missing.join(", "))
+                    };
+                    println!(
+                        "{:<24}\t{:<40}\t{done}/{total} ({:.2} %){locked}",
+                        t.name,
+                        t.dir.display(),
+                        done as f32 / total.max(1) as f32 * 100.0
+                    );
+                }
+                println!();
+            }
+            println!("Progress: {}", progress.summary(&exercises));
         }
 
         Subcommands::Run(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &exercises, &progress)?;
 
-            run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+            match format {
+                Format::Text => run(exercise, options)?,
+                Format::Json => check(exercise, progress.verification(exercise), options)?,
+            }
         }
 
         Subcommands::Hint(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &exercises, &progress)?;
+            let revealed = hint::reveal(exercise, &mut progress, subargs.all).map_err(
+                Error::io(format!("save the progress file {PROGRESS_FILE_PATH}")),
+            )?;
+
+            println!("{}", hint::render(exercise, revealed));
+            if revealed < exercise.hints.len() {
+                println!();
+                println!(
+                    "Run `rustlings hint {}` again for a more detailed hint, or add `--all` to see every hint.",
+                    exercise.name
+                );
+            }
+        }
+
+        Subcommands::Reset(subargs) => {
+            let targets: Vec<&Exercise> = match (&subargs.name, subargs.all) {
+                (None, true) => exercises.iter().collect(),
+                (Some(name), false) => match exercises.iter().find(|e| &e.name == name) {
+                    Some(exercise) => vec![exercise],
+                    None => exercises
+                        .iter()
+                        .filter(|e| e.topic() == Some(name))
+                        .collect(),
+                },
+                _ => {
+                    return Err(Error::Usage(
+                        "Please provide either the name of an exercise or a topic, or --all".into(),
+                    ));
+                }
+            };
+            if targets.is_empty() {
+                return Err(Error::Usage(format!(
+                    "No exercise or topic found for '{}'!",
+                    subargs.name.unwrap()
+                )));
+            }
+            if targets.len() > 1
+                && !subargs.yes
+                && !confirm(&format!(
+                    "This will discard your changes to {} exercises. Continue?",
+                    targets.len()
+                ))
+            {
+                println!("Nothing was reset");
+                return Ok(());
+            }
 
-            println!("{}", exercise.hint);
+            for exercise in targets {
+                match pristine::reset(exercise, &mut progress) {
+                    Ok(true) => println!("Reset {exercise}"),
+                    Ok(false) => {
+                        return Err(Error::Usage(format!(
+                            "There is no original version of {exercise} to reset it to"
+                        )));
+                    }
+                    Err(e) => return Err(Error::Io(format!("reset {exercise}"), e)),
+                }
+            }
         }
 
-        Subcommands::Verify(_subargs) => {
-            verify(&exercises, verbose).unwrap_or_else(|_| std::process::exit(1));
+        Subcommands::Diff(subargs) => {
+            let exercise = find_exercise(&subargs.name, &exercises, &progress)?;
+
+            match diff::diff(exercise) {
+                Ok(Some(diff)) if diff.unified.is_empty() => {
+                    println!("{exercise} hasn't been changed");
+                }
+                Ok(Some(diff)) => {
+                    print!("{}", diff.unified);
+                    if diff.touches_tests {
+                        println!();
+                        warn!("The tests of {} were changed!", exercise);
+                        println!(
+                            "Use `rustlings reset {}` if this was an accident.",
+                            exercise.name
+                        );
+                    }
+                }
+                Ok(None) => {
+                    return Err(Error::Usage(format!(
+                        "There is no original version of {exercise} to compare it to"
+                    )));
+                }
+                Err(e) => return Err(Error::Io(format!("read {exercise}"), e)),
+            }
         }
 
-        Subcommands::Watch(_subargs) => match watch(&exercises, verbose) {
-            Err(e) => {
-                println!("Error: Could not watch your progress. Error message was {e:?}.");
-                println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
-                std::process::exit(1);
+        Subcommands::Solution(subargs) => {
+            let exercise = find_exercise(&subargs.name, &exercises, &progress)?;
+
+            let solution = match solution::read_solution(exercise) {
+                Ok(Some(solution)) => solution,
+                Ok(None) => {
+                    return Err(Error::Usage(format!(
+                        "There is no solution for {exercise} yet"
+                    )));
+                }
+                Err(e) => {
+                    return Err(Error::Io(format!("read the solution for {exercise}"), e));
+                }
+            };
+            if !progress.is_done(exercise) {
+                if !subargs.force {
+                    println!("You haven't completed {exercise} yet!");
+                    println!("Solutions are revealed once `rustlings verify` has verified your exercise.");
+                    println!(
+                        "If you really want to see it now, use `rustlings solution --force {}`.",
+                        exercise.name
+                    );
+                    return Err(Error::Usage(format!("{exercise} isn't done yet")));
+                }
+                if !confirm("Looking at the solution spoils the exercise. Are you sure?") {
+                    println!("The solution wasn't shown");
+                    return Ok(());
+                }
             }
-            Ok(WatchStatus::Finished) => {
-                println!(
-                    "{emoji} All exercises completed! {emoji}",
-                    emoji = Emoji("🎉", "★")
-                );
-                println!();
-                println!("+----------------------------------------------------+");
-                println!("|          You made it to the Fe-nish line!          |");
-                println!("+--------------------------  ------------------------+");
-                println!("                          \\/                         ");
-                println!("     ▒▒          ▒▒▒▒▒▒▒▒      ▒▒▒▒▒▒▒▒          ▒▒   ");
-                println!("   ▒▒▒▒  ▒▒    ▒▒        ▒▒  ▒▒        ▒▒    ▒▒  ▒▒▒▒ ");
-                println!("   ▒▒▒▒  ▒▒  ▒▒            ▒▒            ▒▒  ▒▒  ▒▒▒▒ ");
-                println!(" ░░▒▒▒▒░░▒▒  ▒▒            ▒▒            ▒▒  ▒▒░░▒▒▒▒ ");
-                println!("   ▓▓▓▓▓▓▓▓  ▓▓      ▓▓██  ▓▓  ▓▓██      ▓▓  ▓▓▓▓▓▓▓▓ ");
-                println!("     ▒▒▒▒    ▒▒      ████  ▒▒  ████      ▒▒░░  ▒▒▒▒   ");
-                println!("       ▒▒  ▒▒▒▒▒▒        ▒▒▒▒▒▒        ▒▒▒▒▒▒  ▒▒     ");
-                println!("         ▒▒▒▒▒▒▒▒▒▒▓▓▓▓▓▓▒▒▒▒▒▒▒▒▓▓▒▒▓▓▒▒▒▒▒▒▒▒       ");
-                println!("           ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒         ");
-                println!("             ▒▒▒▒▒▒▒▒▒▒██▒▒▒▒▒▒██▒▒▒▒▒▒▒▒▒▒           ");
-                println!("           ▒▒  ▒▒▒▒▒▒▒▒▒▒██████▒▒▒▒▒▒▒▒▒▒  ▒▒         ");
-                println!("         ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒       ");
-                println!("       ▒▒    ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒    ▒▒     ");
-                println!("       ▒▒  ▒▒    ▒▒                  ▒▒    ▒▒  ▒▒     ");
-                println!("           ▒▒  ▒▒                      ▒▒  ▒▒         ");
-                println!();
-                println!("We hope you enjoyed learning about the various aspects of Rust!");
-                println!(
-                    "If you noticed any issues, please don't hesitate to report them to our repo."
-                );
+
+            if subargs.compare {
+                let mine = solution::read_exercise(exercise).unwrap_or_default();
                 println!(
-                    "You can also contribute your own exercises to help the greater community!"
+                    "{}",
+                    solution::side_by_side(&mine, &solution, solution::terminal_width())
                 );
-                println!();
-                println!("Before reporting an issue or contributing, please read our guidelines:");
-                println!("https://github.com/rust-lang/rustlings/blob/main/CONTRIBUTING.md");
+            } else {
+                print!("{solution}");
             }
-            Ok(WatchStatus::Unfinished) => {
-                println!("We hope you're enjoying learning about Rust!");
-                println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+        }
+
+        Subcommands::Readme(subargs) => {
+            let topic = find_topic(&subargs.topic, &topics)?;
+            let text = readme::load(&topic.readme).map_err(Error::io(format!(
+                "read the README of {} at {}",
+                topic.name,
+                topic.readme.display()
+            )))?;
+            println!("{text}");
+        }
+
+        Subcommands::New(subargs) => {
+            let scaffold = scaffold::new_exercise(
+                Path::new("info.toml"),
+                &topics,
+                &exercises,
+                &subargs.topic,
+                &subargs.name,
+                subargs.mode,
+            )
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::AlreadyExists | std::io::ErrorKind::InvalidInput => {
+                    Error::Usage(format!("Failed to create {}: {e}", subargs.name))
+                }
+                _ => Error::Io(format!("create {}", subargs.name), e),
+            })?;
+            if let Some(readme) = scaffold.readme {
+                println!("Created {}", readme.display());
+            }
+            println!("Created {}", scaffold.exercise.display());
+            println!("Created {}", scaffold.original.display());
+            println!("Added {} to info.toml", subargs.name);
+            println!();
+            println!(
+                "Don't forget to replace the placeholder hint of {} in info.toml.",
+                subargs.name
+            );
+        }
+
+        Subcommands::LintInfo(_) => {
+            let list = ExerciseList {
+                topics,
+                exercises,
+                exercism,
+            };
+            let issues = lint::lint(toml_str, &list, Path::new("exercises"));
+            for issue in &issues {
+                println!("{issue}");
+            }
+            let errors = issues
+                .iter()
+                .filter(|issue| issue.severity == lint::Severity::Error)
+                .count();
+            println!();
+            println!("{errors} errors, {} warnings", issues.len() - errors);
+            if errors > 0 {
+                return Err(Error::Config(format!("info.toml has {errors} errors")));
+            }
+        }
+
+        Subcommands::Dev(subargs) => match subargs.nested {
+            DevSubcommands::Check(_) => {
+                if !dev::check(&exercises) {
+                    return Err(Error::TestFailure("dev check".into()));
+                }
             }
         },
-    }
-}
 
-fn spawn_watch_shell(
-    failed_exercise_hint: &Arc<Mutex<Option<String>>>,
-    should_quit: Arc<AtomicBool>,
-) {
-    let failed_exercise_hint = Arc::clone(failed_exercise_hint);
-    println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
-    thread::spawn(move || loop {
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
-                let input = input.trim();
-                if input == "hint" {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{hint}");
-                    }
-                } else if input == "clear" {
-                    println!("\x1B[2J\x1B[1;1H");
-                } else if input.eq("quit") {
-                    should_quit.store(true, Ordering::SeqCst);
-                    println!("Bye!");
-                } else if input.eq("help") {
-                    println!("Commands available to you in watch mode:");
-                    println!("  hint  - prints the current exercise's hint");
-                    println!("  clear - clears the screen");
-                    println!("  quit  - quits watch mode");
-                    println!("  help  - displays this help message");
+        Subcommands::Verify(subargs) => {
+            let options = VerifyOptions {
+                jobs: subargs.jobs.max(1),
+                keep_going: subargs.keep_going,
+                ..options
+            };
+            let topic = subargs
+                .topic
+                .map(|name| find_topic(&name, &topics))
+                .transpose()?;
+            let targets = exercises
+                .iter()
+                .filter(|e| topic.is_none_or(|topic| topic.contains(e)));
+            verify(targets, &mut progress, options).map_err(|(_, e)| e)?;
+        }
+
+        Subcommands::Watch(subargs) => {
+            let options = VerifyOptions {
+                // Watch mode is meant to be used interactively
+                format: Format::Text,
+                ..options
+            };
+            let tui = subargs.tui && tui::is_supported();
+            if subargs.tui && !tui {
+                println!(
+                    "This terminal can't show the full-screen interface, using the plain one."
+                );
+            }
+            let status = if tui {
+                tui::watch(&exercises, &topics, progress, options)
+            } else {
+                watch(&exercises, &topics, progress, options)
+            };
+            match status {
+                Err(e) => {
+                    let e = match e {
+                        notify::Error::Io(e) => e,
+                        e => std::io::Error::other(e),
+                    };
+                    return Err(Error::Io(
+                        "watch your progress".into(),
+                        std::io::Error::new(
+                            e.kind(),
+                            format!("{e}\nMost likely you've run out of disk space or your 'inotify limit' has been reached."),
+                        ),
+                    ));
+                }
+                Ok(WatchStatus::Finished) => {
+                    println!(
+                        "{emoji} All exercises completed! {emoji}",
+                        emoji = Emoji("🎉", "★")
+                    );
+                    println!();
+                    println!("+----------------------------------------------------+");
+                    println!("|          You made it to the Fe-nish line!          |");
+                    println!("+--------------------------  ------------------------+");
+                    println!("                          \\/                         ");
+                    println!("     ▒▒          ▒▒▒▒▒▒▒▒      ▒▒▒▒▒▒▒▒          ▒▒   ");
+                    println!("   ▒▒▒▒  ▒▒    ▒▒        ▒▒  ▒▒        ▒▒    ▒▒  ▒▒▒▒ ");
+                    println!("   ▒▒▒▒  ▒▒  ▒▒            ▒▒            ▒▒  ▒▒  ▒▒▒▒ ");
+                    println!(" ░░▒▒▒▒░░▒▒  ▒▒            ▒▒            ▒▒  ▒▒░░▒▒▒▒ ");
+                    println!("   ▓▓▓▓▓▓▓▓  ▓▓      ▓▓██  ▓▓  ▓▓██      ▓▓  ▓▓▓▓▓▓▓▓ ");
+                    println!("     ▒▒▒▒    ▒▒      ████  ▒▒  ████      ▒▒░░  ▒▒▒▒   ");
+                    println!("       ▒▒  ▒▒▒▒▒▒        ▒▒▒▒▒▒        ▒▒▒▒▒▒  ▒▒     ");
+                    println!("         ▒▒▒▒▒▒▒▒▒▒▓▓▓▓▓▓▒▒▒▒▒▒▒▒▓▓▒▒▓▓▒▒▒▒▒▒▒▒       ");
+                    println!("           ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒         ");
+                    println!("             ▒▒▒▒▒▒▒▒▒▒██▒▒▒▒▒▒██▒▒▒▒▒▒▒▒▒▒           ");
+                    println!("           ▒▒  ▒▒▒▒▒▒▒▒▒▒██████▒▒▒▒▒▒▒▒▒▒  ▒▒         ");
+                    println!("         ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒       ");
+                    println!("       ▒▒    ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒    ▒▒     ");
+                    println!("       ▒▒  ▒▒    ▒▒                  ▒▒    ▒▒  ▒▒     ");
+                    println!("           ▒▒  ▒▒                      ▒▒  ▒▒         ");
+                    println!();
+                    println!("We hope you enjoyed learning about the various aspects of Rust!");
+                    println!(
+                        "If you noticed any issues, please don't hesitate to report them to our repo."
+                    );
+                    println!(
+                        "You can also contribute your own exercises to help the greater community!"
+                    );
                     println!();
-                    println!("Watch mode automatically re-evaluates the current exercise");
-                    println!("when you edit a file's contents.")
-                } else {
-                    println!("unknown command: {input}");
+                    println!(
+                        "Before reporting an issue or contributing, please read our guidelines:"
+                    );
+                    println!("https://github.com/rust-lang/rustlings/blob/main/CONTRIBUTING.md");
+                }
+                Ok(WatchStatus::Unfinished) => {
+                    println!("We hope you're enjoying learning about Rust!");
+                    println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
                 }
             }
-            Err(error) => println!("error reading command: {error}"),
         }
-    });
+    }
+    Ok(())
 }
 
-fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+fn find_exercise<'a>(
+    name: &str,
+    exercises: &'a [Exercise],
+    progress: &Progress,
+) -> Result<&'a Exercise, Error> {
     if name.eq("next") {
-        exercises
-            .iter()
-            .find(|e| !e.looks_done())
-            .unwrap_or_else(|| {
-                println!("🎉 Congratulations! You have done all the exercises!");
-                println!("🔚 There are no more exercises to do next!");
-                std::process::exit(1)
-            })
+        progress.next(exercises).ok_or_else(|| {
+            Error::Usage(
+                "🎉 Congratulations! You have done all the exercises!\n🔚 There are no more exercises to do next!".into(),
+            )
+        })
     } else {
         exercises
             .iter()
             .find(|e| e.name == name)
-            .unwrap_or_else(|| {
-                println!("No exercise found for '{name}'!");
-                std::process::exit(1)
-            })
+            .ok_or_else(|| Error::Usage(format!("No exercise found for '{name}'!")))
     }
 }
 
-enum WatchStatus {
-    Finished,
-    Unfinished,
-}
-
-fn watch(exercises: &[Exercise], verbose: bool) -> notify::Result<WatchStatus> {
-    /* Clears the terminal with an ANSI escape code.
-    Works in UNIX and newer Windows terminals. */
-    fn clear_screen() {
-        println!("\x1Bc");
-    }
-
-    let (tx, rx) = channel();
-    let should_quit = Arc::new(AtomicBool::new(false));
-
-    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(2))?;
-    watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
-
-    clear_screen();
-
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(exercises.iter(), verbose) {
-        Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
-    };
-    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit));
-    loop {
-        match rx.recv_timeout(Duration::from_secs(1)) {
-            Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
-                        let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .skip_while(|e| !filepath.ends_with(&e.path))
-                            // .filter(|e| filepath.ends_with(&e.path))
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        clear_screen();
-                        match verify(pending_exercises, verbose) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
-                        }
-                    }
-                }
-                _ => {}
-            },
-            Err(RecvTimeoutError::Timeout) => {
-                // the timeout expired, just check the `should_quit` variable below then loop again
-            }
-            Err(e) => println!("watch error: {e:?}"),
-        }
-        // Check if we need to exit
-        if should_quit.load(Ordering::SeqCst) {
-            return Ok(WatchStatus::Unfinished);
-        }
-    }
+fn find_topic<'a>(name: &str, topics: &'a [Topic]) -> Result<&'a Topic, Error> {
+    topics
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| Error::Usage(format!("No topic found for '{name}'!")))
 }
 
 fn rustc_exists() -> bool {
@@ -0,0 +1,206 @@
+use crate::error::Error;
+use crate::exercise::{self, CompileError, Exercise, ExerciseOutput, Mode, Rejection, RunFailure};
+use crate::sandbox::{Sandbox, Violation};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What happened when an exercise was compiled and run.
+/// Nothing is shown to the end user: that's up to the caller.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The exercise didn't compile
+    CompileFailure(ExerciseOutput),
+    /// The exercise compiled, but it's meant to be rejected by the compiler
+    NotRejected,
+    /// The exercise was rejected by the compiler, but not with the expected
+    /// error. The codes of the errors it was rejected with are given
+    WrongError(ExerciseOutput, Vec<String>),
+    /// The exercise compiled, but running it failed
+    RunFailure(ExerciseOutput),
+    /// The exercise ran successfully, but didn't print what it was expected to
+    WrongOutput(ExerciseOutput),
+    /// The exercise compiled, but was killed for running too long
+    Timeout(ExerciseOutput),
+    /// The exercise compiled, but ran into one of the limits of the sandbox
+    LimitExceeded(ExerciseOutput, Violation),
+    /// The exercise compiled and ran successfully.
+    /// Exercises that are only compiled don't have any output
+    Success(Option<ExerciseOutput>),
+    /// The exercise couldn't be compiled or run at all
+    Error(Error),
+}
+
+impl Outcome {
+    /// The error of an exercise that isn't done. Exercises that succeeded
+    /// still have their `I AM NOT DONE` marker then
+    pub fn into_error(self, exercise: &Exercise) -> Error {
+        let name = exercise.name.clone();
+        match self {
+            Outcome::CompileFailure(_) => Error::CompileFailure(name),
+            Outcome::Timeout(_) => Error::Timeout(name),
+            Outcome::Error(error) => error,
+            Outcome::NotRejected
+            | Outcome::WrongError(..)
+            | Outcome::RunFailure(_)
+            | Outcome::WrongOutput(_)
+            | Outcome::LimitExceeded(..)
+            | Outcome::Success(_) => Error::TestFailure(name),
+        }
+    }
+}
+
+/// Compile the given Exercise and, unless it's only meant to be compiled,
+/// run the resulting binary. Also returns how long it took.
+/// `running` is called once the exercise compiled, right before its binary
+/// is run.
+pub fn evaluate(
+    exercise: &Exercise,
+    sandbox: Option<Sandbox>,
+    running: impl FnOnce(),
+) -> (Outcome, Duration) {
+    let start = Instant::now();
+    let compilation = match exercise.compile() {
+        Ok(compilation) => compilation,
+        Err(CompileError::Rejected(output)) => {
+            return (Outcome::CompileFailure(output), start.elapsed())
+        }
+        Err(CompileError::MeantToBeRejected(rejection)) => {
+            let outcome = match rejection {
+                Rejection::Expected(output) => Outcome::Success(Some(output)),
+                Rejection::Unexpected(output, codes) => Outcome::WrongError(output, codes),
+                Rejection::Accepted => Outcome::NotRejected,
+                Rejection::Error(error) => Outcome::Error(error),
+            };
+            return (outcome, start.elapsed());
+        }
+        Err(CompileError::Error(error)) => return (Outcome::Error(error), start.elapsed()),
+    };
+    if let Mode::Clippy = exercise.mode {
+        return (Outcome::Success(None), start.elapsed());
+    }
+
+    running();
+    let outcome = match compilation.run(sandbox.as_ref()) {
+        Ok(output) => Outcome::Success(Some(output)),
+        Err(RunFailure::Failed(output)) => Outcome::RunFailure(output),
+        Err(RunFailure::WrongOutput(output)) => Outcome::WrongOutput(output),
+        Err(RunFailure::TimedOut(output)) => Outcome::Timeout(output),
+        Err(RunFailure::LimitExceeded(output, violation)) => {
+            Outcome::LimitExceeded(output, violation)
+        }
+        Err(RunFailure::Error(error)) => Outcome::Error(error),
+    };
+    (outcome, start.elapsed())
+}
+
+/// Evaluate the exercises on `jobs` worker threads, and pass every outcome
+/// to `handle` in the order the exercises were given, even though they're
+/// evaluated concurrently. Once `handle` returns false, no other exercise
+/// is evaluated.
+pub fn evaluate_all<'a>(
+    exercises: &[&'a Exercise],
+    jobs: usize,
+    sandbox: Option<Sandbox>,
+    mut handle: impl FnMut(&'a Exercise, Outcome, Duration) -> bool,
+) {
+    let next_index = AtomicUsize::new(0);
+    let should_stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let (tx, rx) = channel();
+        for worker in 0..jobs.max(1).min(exercises.len()) {
+            let tx = tx.clone();
+            let (next_index, should_stop) = (&next_index, &should_stop);
+            scope.spawn(move || {
+                exercise::set_worker(worker);
+                while !should_stop.load(Ordering::SeqCst) {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+                    let Some(exercise) = exercises.get(index) else {
+                        break;
+                    };
+                    let evaluation = evaluate(exercise, sandbox, || {});
+                    if tx.send((index, evaluation)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Outcomes are buffered until those of the exercises before them
+        // were handled
+        let mut pending = BTreeMap::new();
+        let mut handled = 0;
+        for (index, evaluation) in rx {
+            pending.insert(index, evaluation);
+            while let Some((outcome, duration)) = pending.remove(&handled) {
+                let exercise = exercises[handled];
+                handled += 1;
+                if !handle(exercise, outcome, duration) {
+                    should_stop.store(true, Ordering::SeqCst);
+                    return;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::ExerciseList;
+
+    const INFO: &str = r#"[[exercises]]
+name = "testSuccess"
+path = "tests/fixture/success/testSuccess.rs"
+mode = "test"
+hint = ""
+
+[[exercises]]
+name = "compFailure"
+path = "tests/fixture/failure/compFailure.rs"
+mode = "compile"
+hint = ""
+
+[[exercises]]
+name = "compSuccess"
+path = "tests/fixture/success/compSuccess.rs"
+mode = "compile"
+hint = ""
+"#;
+
+    #[test]
+    fn test_evaluate_all_in_order() {
+        let list: ExerciseList = toml::from_str(INFO).unwrap();
+        let exercises: Vec<&Exercise> = list.exercises.iter().collect();
+        let mut handled = Vec::new();
+        evaluate_all(&exercises, 3, None, |exercise, outcome, _| {
+            let success = matches!(outcome, Outcome::Success(_));
+            handled.push((exercise.name.as_str(), success));
+            success
+        });
+        // Nothing is handled after the first failure
+        assert_eq!(handled, [("testSuccess", true), ("compFailure", false)]);
+    }
+
+    #[test]
+    fn test_compile_failure_is_an_outcome() {
+        let list: ExerciseList = toml::from_str(INFO).unwrap();
+        let (outcome, _) = evaluate(&list.exercises[1], None, || {
+            panic!("the exercise doesn't compile, so it can't be run")
+        });
+        let Outcome::CompileFailure(output) = outcome else {
+            panic!("compFailure.rs compiled");
+        };
+        assert!(output.stderr.contains("expected pattern"));
+        assert_eq!(
+            Outcome::CompileFailure(output)
+                .into_error(&list.exercises[1])
+                .exit_code(),
+            2
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::exercise::Exercise;
+use crate::progress::Progress;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+// Where the original exercises are kept in the source tree, for build.rs
+// to embed them
+const PRISTINE_DIR: &str = "pristine";
+
+// The original sources of the exercises, keyed by their path in info.toml.
+// Every file of a Cargo project exercise has its own entry.
+// Generated by build.rs from the pristine directory.
+include!(concat!(env!("OUT_DIR"), "/pristine.rs"));
+
+/// Get the files of the exercise as they were shipped, with their paths.
+/// This is empty if there is no original version of the exercise
+pub fn originals(exercise: &Exercise) -> Vec<(&'static Path, &'static str)> {
+    PRISTINE_EXERCISES
+        .iter()
+        .map(|(path, source)| (Path::new(*path), *source))
+        .filter(|(path, _)| path.starts_with(&exercise.path))
+        .collect()
+}
+
+/// Where the original of the file at the given path is kept in the source
+/// tree: the same path in the pristine directory, without its `..`.
+/// Originals only get embedded by the next build
+pub fn snapshot(path: &Path) -> PathBuf {
+    let mut original = PathBuf::from(PRISTINE_DIR);
+    for component in path.components() {
+        if let Component::Normal(name) = component {
+            original.push(name);
+        }
+    }
+    original
+}
+
+/// Restore the files of the exercise to their original source, and forget
+/// that it was done, saving the progress store.
+/// Returns false if there is no original source for this exercise.
+pub fn reset(exercise: &Exercise, progress: &mut Progress) -> io::Result<bool> {
+    reset_in(exercise, progress, Path::new(""))
+}
+
+// Reset the exercise, with its paths relative to the given directory
+fn reset_in(exercise: &Exercise, progress: &mut Progress, root: &Path) -> io::Result<bool> {
+    let originals = originals(exercise);
+    if originals.is_empty() {
+        return Ok(false);
+    }
+    restore(&originals, root)?;
+    progress.forget(exercise);
+    progress.save()?;
+    Ok(true)
+}
+
+// Write the original files, with their paths relative to the given directory
+fn restore(originals: &[(&Path, &str)], root: &Path) -> io::Result<()> {
+    for (path, source) in originals {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, source)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::ExerciseList;
+    use std::process;
+
+    fn exercises() -> ExerciseList {
+        let info = fs::read_to_string("info.toml").unwrap();
+        ExerciseList::load(&info, Path::new("")).unwrap()
+    }
+
+    #[test]
+    fn test_every_exercise_has_an_original() {
+        for exercise in &exercises().exercises {
+            assert!(!originals(exercise).is_empty(), "{exercise}");
+        }
+    }
+
+    #[test]
+    fn test_snapshot_paths() {
+        assert_eq!(
+            snapshot(Path::new("exercises/intro/intro1.rs")),
+            Path::new("pristine/exercises/intro/intro1.rs")
+        );
+        assert_eq!(
+            snapshot(Path::new("../exercism/hello-world/src/lib.rs")),
+            Path::new("pristine/exercism/hello-world/src/lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_reset_restores_the_shipped_exercise() {
+        let list = exercises();
+        let exercise = list.exercises.iter().find(|e| e.name == "intro1").unwrap();
+        let originals = originals(exercise);
+        assert_eq!(originals.len(), 1);
+        let (path, original) = originals[0];
+        assert_eq!(path, Path::new("exercises/intro/intro1.rs"));
+        assert!(original.starts_with("// intro1.rs\n// About this `I AM NOT DONE` thing:\n"));
+        assert!(original.contains("Good luck!"));
+
+        let scratch = std::env::temp_dir().join(format!("rustlings_pristine_{}", process::id()));
+        fs::create_dir_all(scratch.join(path.parent().unwrap())).unwrap();
+        fs::write(scratch.join(path), format!("{original}// my edit\n")).unwrap();
+        let mut progress = Progress::load(scratch.join("progress.toml")).unwrap();
+        progress.mark_done(exercise).unwrap();
+        assert!(reset_in(exercise, &mut progress, &scratch).unwrap());
+        assert_eq!(fs::read_to_string(scratch.join(path)).unwrap(), original);
+
+        // The exercise is no longer done, as far as the saved store goes too
+        assert!(!progress.is_done(exercise));
+        let saved = Progress::load(scratch.join("progress.toml")).unwrap();
+        assert!(!saved.is_done(exercise));
+        fs::remove_dir_all(scratch).unwrap();
+    }
+}
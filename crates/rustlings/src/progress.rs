@@ -0,0 +1,313 @@
+use crate::exercise::{Exercise, Mode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The progress store lives next to info.toml
+pub const PROGRESS_FILE_PATH: &str = ".rustlings-state.toml";
+
+// FNV-1a parameters, used to fingerprint exercise sources.
+// The hash only has to be stable across runs and toolchains,
+// not cryptographically strong.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The verification status of an exercise according to the progress store
+#[derive(Serialize, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Verification {
+    /// The exercise passed `verify` and its source hasn't changed since
+    #[serde(rename = "done")]
+    Verified,
+    /// The exercise passed `verify`, but its source has been edited since
+    #[serde(rename = "changed")]
+    Outdated,
+    /// The exercise never passed `verify`
+    #[serde(rename = "pending")]
+    Unverified,
+}
+
+impl Verification {
+    /// The status shown to the end user
+    pub fn label(self) -> &'static str {
+        match self {
+            Verification::Verified => "Done",
+            Verification::Outdated => "Changed",
+            Verification::Unverified => "Pending",
+        }
+    }
+}
+
+/// A single entry of the progress store
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug)]
+pub struct Record {
+    /// The hash of the exercise source at the time it was verified
+    pub hash: String,
+}
+
+/// The persisted record of every exercise that was verified as done.
+/// This is (de)serialized from the .rustlings-state.toml file
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub struct Progress {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    exercises: BTreeMap<String, Record>,
+    // How many hints of each exercise have been revealed so far
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    hints: BTreeMap<String, usize>,
+}
+
+impl Progress {
+    /// Load the progress store from the given path.
+    /// A missing file simply means that nothing has been verified yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut progress = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str::<Progress>(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Progress::default(),
+            Err(e) => return Err(e),
+        };
+        progress.path = path.to_path_buf();
+        Ok(progress)
+    }
+
+    /// Write the progress store back to disk.
+    /// The file is replaced atomically so that concurrent readers never see
+    /// a partially written store.
+    pub fn save(&self) -> io::Result<()> {
+        let content =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &self.path)
+    }
+
+    /// Record the exercise as done, fingerprinting its current source
+    pub fn mark_done(&mut self, exercise: &Exercise) -> io::Result<()> {
+        let hash = hash_exercise(exercise)?;
+        self.exercises
+            .insert(exercise.name.clone(), Record { hash });
+        Ok(())
+    }
+
+    /// Drop the record of the exercise, e.g. once it has been reset
+    pub fn forget(&mut self, exercise: &Exercise) {
+        self.exercises.remove(&exercise.name);
+    }
+
+    /// Compare the stored record of the exercise with its current source
+    pub fn verification(&self, exercise: &Exercise) -> Verification {
+        match self.exercises.get(&exercise.name) {
+            None => Verification::Unverified,
+            Some(record) => match hash_exercise(exercise) {
+                Ok(hash) if hash == record.hash => Verification::Verified,
+                _ => Verification::Outdated,
+            },
+        }
+    }
+
+    /// How many hints of the exercise the end user has seen
+    pub fn hints_revealed(&self, exercise: &Exercise) -> usize {
+        self.hints.get(&exercise.name).copied().unwrap_or(0)
+    }
+
+    /// Remember that the first `count` hints of the exercise have been seen.
+    /// Hints are never hidden again once they've been revealed.
+    pub fn reveal_hints(&mut self, exercise: &Exercise, count: usize) {
+        let revealed = self.hints.entry(exercise.name.clone()).or_default();
+        *revealed = (*revealed).max(count);
+    }
+
+    /// Check that the exercise was verified and hasn't changed since
+    pub fn is_done(&self, exercise: &Exercise) -> bool {
+        self.verification(exercise) == Verification::Verified
+    }
+
+    /// The names of the given exercises that are done.
+    /// Telling reads the files of every exercise, so the set is meant to be
+    /// computed once per command or render and passed around
+    pub fn done<'a>(&self, exercises: &'a [Exercise]) -> HashSet<&'a str> {
+        exercises
+            .iter()
+            .filter(|e| self.is_done(e))
+            .map(|e| e.name.as_str())
+            .collect()
+    }
+
+    /// The status of the exercise shown to the end user, who can't work on
+    /// locked exercises yet
+    pub fn label(
+        &self,
+        exercise: &Exercise,
+        exercises: &[Exercise],
+        done: &HashSet<&str>,
+    ) -> &'static str {
+        if done.contains(exercise.name.as_str()) {
+            Verification::Verified.label()
+        } else if !is_unlocked(exercise, exercises, done) {
+            "Locked"
+        } else {
+            self.verification(exercise).label()
+        }
+    }
+
+    /// The first exercise that isn't done yet and is unlocked.
+    /// If every remaining exercise is locked, the first of them is returned
+    pub fn next<'a>(&self, exercises: &'a [Exercise]) -> Option<&'a Exercise> {
+        let done = self.done(exercises);
+        let mut remaining = exercises
+            .iter()
+            .filter(|e| !done.contains(e.name.as_str()))
+            .peekable();
+        let first = remaining.peek().copied();
+        remaining
+            .find(|e| is_unlocked(e, exercises, &done))
+            .or(first)
+    }
+
+    /// How far along the end user is with the given exercises
+    pub fn summary(&self, exercises: &[Exercise]) -> String {
+        let done = self.done(exercises).len();
+        format!(
+            "You completed {} / {} exercises ({:.2} %).",
+            done,
+            exercises.len(),
+            // Without exercises, none of them was completed
+            done as f32 / exercises.len().max(1) as f32 * 100.0
+        )
+    }
+}
+
+/// The requirements of an exercise or topic that aren't done yet:
+/// exercises that aren't done, and topics with exercises that aren't done.
+/// `done` holds the names of the exercises that are done, see [`Progress::done`]
+pub fn missing_requirements<'a>(
+    requires: &'a [String],
+    exercises: &[Exercise],
+    done: &HashSet<&str>,
+) -> Vec<&'a str> {
+    requires
+        .iter()
+        .filter(|name| {
+            exercises.iter().any(|e| {
+                (e.name == **name || e.topic.as_ref() == Some(*name))
+                    && !done.contains(e.name.as_str())
+            })
+        })
+        .map(String::as_str)
+        .collect()
+}
+
+/// Whether or not every requirement of the exercise is done
+pub fn is_unlocked(exercise: &Exercise, exercises: &[Exercise], done: &HashSet<&str>) -> bool {
+    missing_requirements(&exercise.requires, exercises, done).is_empty()
+}
+
+// Fingerprint the contents of the files of an exercise
+fn hash_exercise(exercise: &Exercise) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    for file in exercise.files()? {
+        // Moving code between the files of a Cargo project changes it too
+        if let Mode::Cargo | Mode::Exercism = exercise.mode {
+            let relative = file.strip_prefix(&exercise.path).unwrap_or(&file);
+            bytes.extend_from_slice(relative.to_string_lossy().as_bytes());
+            bytes.push(0);
+        }
+        bytes.extend(fs::read(&file)?);
+    }
+    Ok(format!("{:016x}", hash_bytes(&bytes)))
+}
+
+/// Hash bytes with 64-bit FNV-1a, which, unlike the hasher of the standard
+/// library, gives the same result on every run and toolchain
+pub fn hash_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise {
+            name: name.into(),
+            path: PathBuf::from(path),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hash_is_stable() {
+        assert_eq!(hash_bytes(b""), FNV_OFFSET_BASIS);
+        assert_eq!(hash_bytes(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_summary_without_exercises() {
+        assert_eq!(
+            Progress::default().summary(&[]),
+            "You completed 0 / 0 exercises (0.00 %)."
+        );
+    }
+
+    #[test]
+    fn test_missing_store_is_empty() {
+        let progress = Progress::load("tests/fixture/state/does_not_exist.toml").unwrap();
+        let exercise = exercise(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+        );
+        assert_eq!(progress.verification(&exercise), Verification::Unverified);
+    }
+
+    #[test]
+    fn test_mark_done_then_outdated() {
+        let mut progress = Progress::default();
+        let finished = exercise(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+        );
+        progress.mark_done(&finished).unwrap();
+        assert!(progress.is_done(&finished));
+
+        // Same name, different source: the stored hash no longer matches
+        let edited = exercise(
+            "finished_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        assert_eq!(progress.verification(&edited), Verification::Outdated);
+    }
+
+    #[test]
+    fn test_next_exercise_is_unlocked() {
+        let mut progress = Progress::default();
+        let mut quiz = exercise("quiz1", "tests/fixture/state/finished_exercise.rs");
+        quiz.topic = Some("quiz1".into());
+        let mut locked = exercise("locked", "tests/fixture/state/pending_exercise.rs");
+        locked.requires = vec!["quiz1".into()];
+        let mut unlocked = exercise("unlocked", "tests/fixture/state/pending_exercise.rs");
+        unlocked.requires = vec!["missing".into()];
+        let exercises = [locked, unlocked, quiz];
+
+        let done = progress.done(&exercises);
+        assert_eq!(
+            missing_requirements(&exercises[0].requires, &exercises, &done),
+            ["quiz1"]
+        );
+        assert!(is_unlocked(&exercises[1], &exercises, &done));
+        assert_eq!(progress.label(&exercises[0], &exercises, &done), "Locked");
+        assert_eq!(progress.next(&exercises).unwrap().name, "unlocked");
+
+        progress.mark_done(&exercises[2]).unwrap();
+        let done = progress.done(&exercises);
+        assert_eq!(done, HashSet::from(["quiz1"]));
+        assert!(is_unlocked(&exercises[0], &exercises, &done));
+        assert_eq!(progress.next(&exercises).unwrap().name, "locked");
+    }
+}
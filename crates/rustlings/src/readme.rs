@@ -0,0 +1,128 @@
+use crate::exercise::{Exercise, Mode, Topic};
+use console::style;
+use regex::{Captures, Regex};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap());
+static CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`]+)`").unwrap());
+static BOLD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*\*([^*]+)\*\*").unwrap());
+
+/// Where the README introducing the exercise is: the README of its topic,
+/// or for an Exercism crate, its own. Exercises without a topic have none
+pub fn path(exercise: &Exercise, topics: &[Topic]) -> Option<PathBuf> {
+    if let Mode::Exercism = exercise.mode {
+        return Some(exercise.path.join("README.md"));
+    }
+    topics
+        .iter()
+        .find(|topic| exercise.topic() == Some(topic.name.as_str()))
+        .map(|topic| topic.readme.clone())
+}
+
+/// Read the README at the given path, rendered for the terminal
+pub fn load(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map(|markdown| render(&markdown))
+}
+
+/// Render the Markdown of a topic README for the terminal: headings are
+/// highlighted, code is set apart, and links show where they point to
+pub fn render(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        let rendered = if in_code_block {
+            format!("    {}", style(line).dim())
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            style(heading.to_uppercase())
+                .bold()
+                .underlined()
+                .to_string()
+        } else if line.starts_with("##") {
+            style(line.trim_start_matches('#').trim_start())
+                .bold()
+                .to_string()
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            format!("  • {}", render_inline(item))
+        } else {
+            render_inline(line)
+        };
+        lines.push(rendered);
+    }
+    lines.join("\n")
+}
+
+// Render links, inline code and emphasis within a line
+fn render_inline(line: &str) -> String {
+    let line = LINK.replace_all(line, |caps: &Captures| {
+        format!("{} ({})", &caps[1], style(&caps[2]).underlined())
+    });
+    let line = CODE.replace_all(&line, |caps: &Captures| {
+        style(&caps[1]).yellow().to_string()
+    });
+    let line = BOLD.replace_all(&line, |caps: &Captures| style(&caps[1]).bold().to_string());
+    line.into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::ExerciseList;
+
+    #[test]
+    fn test_readme_paths() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[topics]]
+            name = "quiz1"
+            dir = "exercises"
+            readme = "exercises/QUIZ.md"
+
+            [[topics.exercises]]
+            name = "quiz1"
+            path = "exercises/quiz1.rs"
+            mode = "compile"
+            hint = ""
+
+            [[exercises]]
+            name = "extra"
+            path = "exercises/extra.rs"
+            mode = "compile"
+            hint = ""
+            "#,
+        )
+        .unwrap();
+        let readme = |exercise: &Exercise| path(exercise, &list.topics);
+        assert_eq!(
+            readme(&list.exercises[0]),
+            Some(PathBuf::from("exercises/QUIZ.md"))
+        );
+        assert_eq!(readme(&list.exercises[1]), None);
+        let exercism = Exercise {
+            path: PathBuf::from("../exercism/hello-world"),
+            mode: Mode::Exercism,
+            topic: Some("exercism".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            readme(&exercism),
+            Some(PathBuf::from("../exercism/hello-world/README.md"))
+        );
+    }
+
+    #[test]
+    fn test_render_readme() {
+        let markdown = "# Variables\n\nIn Rust, variables are `immutable` by **default**.\n\n## Further information\n\n- [Variables](https://doc.rust-lang.org/book/ch03-01.html)\n\n```rust\nlet x = 5;\n```\n";
+        let rendered = console::strip_ansi_codes(&render(markdown)).into_owned();
+        assert_eq!(
+            rendered,
+            "VARIABLES\n\nIn Rust, variables are immutable by default.\n\nFurther information\n\n  • Variables (https://doc.rust-lang.org/book/ch03-01.html)\n\n    let x = 5;"
+        );
+    }
+}
@@ -1,17 +1,19 @@
-use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
+use crate::verify::{report_failure, test, VerifyOptions};
 use indicatif::ProgressBar;
+use rustlings::error::Error;
+use rustlings::exercise::{Exercise, Mode};
+use rustlings::outcome::{evaluate, Outcome};
 use std::time::Duration;
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
-// The verbose argument helps determine whether or not to show
+// The verbose option helps determine whether or not to show
 // the output from the test harnesses (if the mode of the exercise is test)
-pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
+pub fn run(exercise: &Exercise, options: VerifyOptions) -> Result<(), Error> {
     match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
-        Mode::Compile => compile_and_run(exercise)?,
-        Mode::Clippy => compile_and_run(exercise)?,
+        Mode::Test | Mode::CompileFail | Mode::Cargo | Mode::Exercism => test(exercise, options)?,
+        Mode::Compile => compile_and_run(exercise, options)?,
+        Mode::Clippy => compile_and_run(exercise, options)?,
     }
     Ok(())
 }
@@ -19,41 +21,37 @@ pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
 // Invoke the rust compiler on the path of the given exercise
 // and run the ensuing binary.
 // This is strictly for non-test binaries, so output is displayed
-fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
+fn compile_and_run(exercise: &Exercise, options: VerifyOptions) -> Result<(), Error> {
     let progress_bar = ProgressBar::new_spinner();
     progress_bar.set_message(format!("Compiling {exercise}..."));
     progress_bar.enable_steady_tick(Duration::from_millis(100));
 
-    let compilation_result = exercise.compile();
-    let compilation = match compilation_result {
-        Ok(compilation) => compilation,
-        Err(output) => {
-            progress_bar.finish_and_clear();
-            warn!(
-                "Compilation of {} failed!, Compiler error message:\n",
-                exercise
-            );
-            println!("{}", output.stderr);
-            return Err(());
-        }
-    };
-
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
+    let (outcome, _) = evaluate(exercise, options.sandbox, || {
+        progress_bar.set_message(format!("Running {exercise}..."));
+    });
     progress_bar.finish_and_clear();
 
-    match result {
-        Ok(output) => {
-            println!("{}", output.stdout);
+    match outcome {
+        Outcome::Success(output) => {
+            // Clippy exercises are only checked
+            if let Some(output) = output {
+                println!("{}", output.stdout);
+            }
             success!("Successfully ran {}", exercise);
             Ok(())
         }
-        Err(output) => {
-            println!("{}", output.stdout);
+        Outcome::CompileFailure(output) => {
+            warn!(
+                "Compilation of {} failed!, Compiler error message:\n",
+                exercise
+            );
             println!("{}", output.stderr);
-
-            warn!("Ran {} with errors", exercise);
-            Err(())
+            Err(Error::CompileFailure(exercise.name.clone()))
+        }
+        Outcome::Error(error) => Err(error),
+        outcome => {
+            report_failure(exercise, &outcome);
+            Err(outcome.into_error(exercise))
         }
     }
 }
@@ -0,0 +1,271 @@
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+use std::time::Duration;
+
+/// Limits applied to learner binaries when running in the sandbox
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Sandbox {
+    /// The size of the virtual address space, in bytes.
+    /// Test harnesses reserve a lot of address space for their threads,
+    /// so this is more generous than the memory exercises actually need
+    pub address_space: u64,
+    /// The CPU time, in seconds
+    pub cpu_seconds: u64,
+    /// The number of open file descriptors
+    pub open_files: u64,
+    /// The number of processes and threads, on top of those the user already runs
+    pub processes: u64,
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Sandbox {
+            address_space: 2 * 1024 * 1024 * 1024,
+            cpu_seconds: 20,
+            open_files: 64,
+            processes: 64,
+        }
+    }
+}
+
+/// The limit a sandboxed binary ran into
+#[derive(Serialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Violation {
+    /// It ran out of memory
+    Memory,
+    /// It used up its CPU time
+    CpuTime,
+    /// It tried to spawn too many processes or threads, e.g. a fork bomb
+    Processes,
+    /// It opened too many files
+    OpenFiles,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let description = match self {
+            Violation::Memory => "ran out of memory",
+            Violation::CpuTime => "used up its CPU time",
+            Violation::Processes => "spawned too many processes or threads",
+            Violation::OpenFiles => "opened too many files",
+        };
+        write!(f, "{description}")
+    }
+}
+
+impl Sandbox {
+    /// Confine the command: run it with a clean environment from the given
+    /// throwaway directory, and apply the resource limits once it's forked
+    #[cfg(target_os = "linux")]
+    pub fn confine(&self, command: &mut Command, work_dir: &Path) -> io::Result<()> {
+        use std::os::unix::process::CommandExt;
+
+        let processes = current_user_tasks()? + self.processes;
+        let limits = [
+            (libc::RLIMIT_AS, self.address_space, self.address_space),
+            // SIGXCPU is sent at the soft limit, SIGKILL at the hard one
+            (libc::RLIMIT_CPU, self.cpu_seconds, self.cpu_seconds + 1),
+            (libc::RLIMIT_NOFILE, self.open_files, self.open_files),
+            (libc::RLIMIT_NPROC, processes, processes),
+        ];
+
+        command
+            .current_dir(work_dir)
+            .env_clear()
+            .env("PATH", "/usr/bin:/bin")
+            .env("HOME", work_dir);
+        // SAFETY: setrlimit is async-signal-safe, and nothing is allocated
+        // between fork and exec.
+        unsafe {
+            command.pre_exec(move || {
+                for (resource, soft, hard) in limits {
+                    let limit = libc::rlimit {
+                        rlim_cur: soft as libc::rlim_t,
+                        rlim_max: hard as libc::rlim_t,
+                    };
+                    if libc::setrlimit(resource, &limit) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn confine(&self, _command: &mut Command, _work_dir: &Path) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the sandbox is only available on Linux",
+        ))
+    }
+
+    /// Find out which limit, if any, made a confined binary fail.
+    /// The CPU and memory limits are told apart by the signal that killed
+    /// the binary and the CPU time it used. The process and file limits only
+    /// make system calls fail, so they're recognized by the error code the
+    /// Rust runtime reports on stderr.
+    #[cfg(unix)]
+    pub fn detect_violation(
+        &self,
+        status: &ExitStatus,
+        cpu_time: Option<Duration>,
+        stderr: &str,
+    ) -> Option<Violation> {
+        use std::os::unix::process::ExitStatusExt;
+
+        let cpu_limit = Duration::from_secs(self.cpu_seconds);
+        match status.signal() {
+            // SIGXCPU is sent at the soft limit, SIGKILL at the hard one
+            Some(libc::SIGXCPU) => return Some(Violation::CpuTime),
+            Some(libc::SIGKILL) if cpu_time.is_some_and(|time| time >= cpu_limit) => {
+                return Some(Violation::CpuTime)
+            }
+            // The Rust runtime aborts when an allocation fails, after saying
+            // so. Any other abort is just a failure
+            Some(libc::SIGABRT) if reports_failed_allocation(stderr) => {
+                return Some(Violation::Memory)
+            }
+            _ => {}
+        }
+        if status.success() {
+            return None;
+        }
+        [
+            (libc::ENOMEM, Violation::Memory),
+            (libc::EAGAIN, Violation::Processes),
+            (libc::EMFILE, Violation::OpenFiles),
+        ]
+        .into_iter()
+        .find(|(code, _)| reports_os_error(stderr, *code))
+        .map(|(_, violation)| violation)
+    }
+
+    #[cfg(not(unix))]
+    pub fn detect_violation(
+        &self,
+        _status: &ExitStatus,
+        _cpu_time: Option<Duration>,
+        _stderr: &str,
+    ) -> Option<Violation> {
+        None
+    }
+}
+
+// Whether or not the output mentions an OS error with the given code,
+// as io::Error formats it with either Display or Debug
+#[cfg(unix)]
+fn reports_os_error(output: &str, code: i32) -> bool {
+    output.contains(&format!("(os error {code})")) || output.contains(&format!("code: {code},"))
+}
+
+// Whether or not the output has the message the Rust runtime prints when
+// it fails to allocate memory
+#[cfg(unix)]
+fn reports_failed_allocation(output: &str) -> bool {
+    output
+        .lines()
+        .any(|line| line.starts_with("memory allocation of ") && line.ends_with(" failed"))
+}
+
+// Count the processes and threads the current user is running.
+// RLIMIT_NPROC applies to all of them, not only to the sandboxed binary.
+#[cfg(target_os = "linux")]
+fn current_user_tasks() -> io::Result<u64> {
+    // SAFETY: getuid always succeeds
+    let uid = unsafe { libc::getuid() }.to_string();
+    let mut tasks = 0;
+    for entry in std::fs::read_dir("/proc")? {
+        let path = entry?.path().join("status");
+        // Processes can exit while we're looking at them
+        let Ok(status) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let field = |name: &str| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix(name))
+                .and_then(|value| value.split_whitespace().next().map(str::to_string))
+        };
+        if field("Uid:").as_deref() == Some(uid.as_str()) {
+            tasks += field("Threads:").and_then(|t| t.parse().ok()).unwrap_or(1);
+        }
+    }
+    Ok(tasks)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::*;
+    use crate::supervisor::output_with_timeout;
+
+    const SANDBOX: Sandbox = Sandbox {
+        address_space: 2 * 1024 * 1024 * 1024,
+        cpu_seconds: 1,
+        open_files: 16,
+        processes: 64,
+    };
+
+    fn run_confined(script: &str) -> (ExitStatus, Option<Duration>, String) {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        SANDBOX.confine(&mut command, Path::new("/")).unwrap();
+        let supervised = output_with_timeout(&mut command, None, Duration::from_secs(10)).unwrap();
+        let stderr = String::from_utf8_lossy(&supervised.output.stderr).to_string();
+        (supervised.output.status, supervised.cpu_time, stderr)
+    }
+
+    #[test]
+    fn test_environment_is_cleared() {
+        let (status, ..) = run_confined("test -z \"$CARGO\" && test \"$PWD\" = /");
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_limits_are_applied() {
+        let (status, ..) = run_confined("test \"$(ulimit -n)\" = 16");
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_cpu_limit_is_detected_from_the_signal() {
+        let (status, cpu_time, stderr) = run_confined("while :; do :; done");
+        assert_eq!(
+            SANDBOX.detect_violation(&status, cpu_time, &stderr),
+            Some(Violation::CpuTime)
+        );
+    }
+
+    #[test]
+    fn test_detect_violation() {
+        let (status, cpu_time, _) = run_confined("exit 101");
+        assert_eq!(
+            SANDBOX.detect_violation(&status, cpu_time, "failed to spawn thread: Os { code: 11, kind: WouldBlock, message: \"Resource temporarily unavailable\" }"),
+            Some(Violation::Processes)
+        );
+        assert_eq!(
+            SANDBOX.detect_violation(&status, cpu_time, "Error: Os { code: 24, kind: Uncategorized, message: \"No file descriptors available\" }"),
+            Some(Violation::OpenFiles)
+        );
+        assert_eq!(
+            SANDBOX.detect_violation(&status, cpu_time, "assertion failed"),
+            None
+        );
+        let (status, cpu_time, _) = run_confined("kill -ABRT $$");
+        assert_eq!(
+            SANDBOX.detect_violation(
+                &status,
+                cpu_time,
+                "memory allocation of 8589934592 bytes failed\n"
+            ),
+            Some(Violation::Memory)
+        );
+        // Aborting has other reasons than running out of memory
+        assert_eq!(SANDBOX.detect_violation(&status, cpu_time, ""), None);
+    }
+}
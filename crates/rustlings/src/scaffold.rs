@@ -0,0 +1,353 @@
+use crate::exercise::{Exercise, ExerciseList, Topic};
+use crate::pristine;
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The kinds of exercises that can be scaffolded
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Template {
+    Compile,
+    Test,
+    Clippy,
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "compile" => Ok(Template::Compile),
+            "test" => Ok(Template::Test),
+            "clippy" => Ok(Template::Clippy),
+            _ => Err(format!(
+                "unknown mode '{s}', expected 'compile', 'test' or 'clippy'"
+            )),
+        }
+    }
+}
+
+impl Template {
+    // The mode of the exercise in info.toml
+    fn mode(self) -> &'static str {
+        match self {
+            Template::Compile => "compile",
+            Template::Test => "test",
+            Template::Clippy => "clippy",
+        }
+    }
+
+    // The source the exercise starts out with
+    fn source(self, name: &str) -> String {
+        match self {
+            Template::Compile => format!(
+                "// {name}.rs
+// Make me compile! Execute `rustlings hint {name}` for hints :)
+
+// I AM NOT DONE
+
+fn main() {{}}
+"
+            ),
+            Template::Test => format!(
+                "// {name}.rs
+// Make the tests pass! Execute `rustlings hint {name}` for hints :)
+
+// I AM NOT DONE
+
+fn answer() -> u32 {{
+    0
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn it_works() {{
+        assert_eq!(answer(), 42);
+    }}
+}}
+"
+            ),
+            Template::Clippy => format!(
+                "// {name}.rs
+// Make clippy happy! Every warning clippy gives fails the exercise.
+// Execute `rustlings hint {name}` for hints :)
+
+// I AM NOT DONE
+
+fn main() {{}}
+"
+            ),
+        }
+    }
+}
+
+// What `new` created
+pub struct Scaffold {
+    pub exercise: PathBuf,
+    // Its original, for `reset` and `diff`
+    pub original: PathBuf,
+    // Set if the topic didn't have a README yet
+    pub readme: Option<PathBuf>,
+}
+
+// Create a new exercise at the end of the topic: its source file and its
+// original, its entry in info.toml, and the directory and README of the
+// topic if they're missing
+pub fn new_exercise(
+    info_path: &Path,
+    topics: &[Topic],
+    exercises: &[Exercise],
+    topic: &str,
+    name: &str,
+    template: Template,
+) -> io::Result<Scaffold> {
+    // Both end up in paths, and the name in Rust code too
+    for (what, value) in [("topic", topic), ("exercise", name)] {
+        if !is_identifier(value) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the name of the {what} isn't an identifier: '{value}'"),
+            ));
+        }
+    }
+    if exercises.iter().any(|e| e.name == name) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("there already is an exercise named '{name}'"),
+        ));
+    }
+    let existing = topics.iter().find(|t| t.name == topic);
+    let dir = existing.map_or_else(|| Path::new("exercises").join(topic), |t| t.dir.clone());
+    let exercise = dir.join(format!("{name}.rs"));
+    if exercise.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", exercise.display()),
+        ));
+    }
+
+    let info = fs::read_to_string(info_path)?;
+    let info = insert_entry(&info, topic, &entry(name, &exercise, template));
+    // Don't break info.toml if the insertion went wrong somehow
+    toml::from_str::<ExerciseList>(&info)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    fs::create_dir_all(&dir)?;
+    let readme = match existing {
+        Some(Topic { readme, .. }) if readme.exists() => None,
+        Some(Topic { readme, .. }) => Some(readme.clone()),
+        None => Some(dir.join("README.md")),
+    };
+    if let Some(readme) = &readme {
+        fs::write(readme, readme_template(topic))?;
+    }
+    let source = template.source(name);
+    let original = pristine::snapshot(&exercise);
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&original, &source)?;
+    fs::write(&exercise, source)?;
+    fs::write(info_path, info)?;
+    Ok(Scaffold {
+        exercise,
+        original,
+        readme,
+    })
+}
+
+// Letters, digits and underscores, not starting with a digit
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The info.toml entry of the exercise. The name and path are written as
+// TOML strings, with the path separated by `/` like every other entry
+fn entry(name: &str, path: &Path, template: Template) -> String {
+    let path = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!(
+        "[[topics.exercises]]
+name = {}
+path = {}
+mode = \"{}\"
+hint = \"\"\"
+TODO: write a hint for {name}.\"\"\"
+",
+        toml::Value::String(name.to_string()),
+        toml::Value::String(path),
+        template.mode()
+    )
+}
+
+fn readme_template(topic: &str) -> String {
+    format!(
+        "# {topic}
+
+TODO: introduce {topic}.
+
+## Further information
+
+- [The Rust Programming Language](https://doc.rust-lang.org/book/)
+"
+    )
+}
+
+// Insert the entry of an exercise after the last exercise of the topic,
+// keeping the rest of info.toml as it is. A topic that isn't in info.toml
+// yet is added at the end.
+fn insert_entry(info: &str, topic: &str, entry: &str) -> String {
+    let name = Regex::new(r#"^\s*name\s*=\s*"([^"]*)"\s*$"#).unwrap();
+    let lines: Vec<&str> = info.lines().collect();
+    let is_header = |line: &str| line.trim_start().starts_with('[');
+    // Tables within a topic, such as its exercises, start with `topics.`
+    let ends_topic = |line: &str| {
+        let line = line.trim_start();
+        is_header(line) && !line.starts_with("[[topics.") && !line.starts_with("[topics.")
+    };
+
+    let start = lines.iter().enumerate().position(|(i, line)| {
+        line.trim() == "[[topics]]"
+            && lines[i + 1..]
+                .iter()
+                .take_while(|line| !is_header(line))
+                .any(|line| name.captures(line).is_some_and(|c| &c[1] == topic))
+    });
+    let Some(start) = start else {
+        let mut info = info.trim_end().to_string();
+        info.push_str(&format!("\n\n[[topics]]\nname = \"{topic}\"\n\n{entry}"));
+        return info;
+    };
+
+    let mut end = lines[start + 1..]
+        .iter()
+        .position(|line| ends_topic(line))
+        .map_or(lines.len(), |i| start + 1 + i);
+    // Comments right before the next topic are about that topic
+    while end > start + 1 && {
+        let line = lines[end - 1].trim();
+        line.is_empty() || line.starts_with('#')
+    } {
+        end -= 1;
+    }
+
+    let mut result = lines[..end].join("\n");
+    result.push_str("\n\n");
+    result.push_str(entry);
+    let rest = lines[end..].join("\n");
+    if !rest.trim().is_empty() {
+        result.push('\n');
+        result.push_str(rest.trim_start());
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INFO: &str = r#"# The exercises
+[[topics]]
+name = "intro"
+
+[[topics.exercises]]
+name = "intro1"
+path = "exercises/intro/intro1.rs"
+mode = "compile"
+hint = ""
+
+# Variables come next
+[[topics]]
+name = "variables"
+
+[[topics.exercises]]
+name = "variables1"
+path = "exercises/variables/variables1.rs"
+mode = "compile"
+hint = ""
+"#;
+
+    #[test]
+    fn test_insert_after_last_exercise_of_topic() {
+        let entry = entry(
+            "intro2",
+            Path::new("exercises/intro/intro2.rs"),
+            Template::Test,
+        );
+        let info = insert_entry(INFO, "intro", &entry);
+        let list: ExerciseList = toml::from_str(&info).unwrap();
+        let names: Vec<&str> = list.exercises.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["intro1", "intro2", "variables1"]);
+        assert_eq!(list.exercises[1].hints, ["TODO: write a hint for intro2."]);
+        // The comment about the next topic stays with it
+        assert!(info.contains("for intro2.\"\"\"\n\n# Variables come next\n[[topics]]"));
+    }
+
+    #[test]
+    fn test_insert_into_last_topic() {
+        let entry = entry(
+            "variables2",
+            Path::new("exercises/variables/variables2.rs"),
+            Template::Compile,
+        );
+        let info = insert_entry(INFO, "variables", &entry);
+        assert!(info.starts_with(INFO));
+        let list: ExerciseList = toml::from_str(&info).unwrap();
+        assert_eq!(list.exercises[2].name, "variables2");
+    }
+
+    #[test]
+    fn test_insert_into_new_topic() {
+        let entry = entry(
+            "functions1",
+            Path::new("exercises/functions/functions1.rs"),
+            Template::Clippy,
+        );
+        let info = insert_entry(INFO, "functions", &entry);
+        let list: ExerciseList = toml::from_str(&info).unwrap();
+        assert_eq!(list.topics[2].name, "functions");
+        assert_eq!(list.exercises[2].topic(), Some("functions"));
+    }
+
+    #[test]
+    fn test_entry_paths_are_toml_strings() {
+        let path = Path::new("exercises")
+            .join("we\\ird \"topic\"")
+            .join("ex1.rs");
+        let entry = entry("ex1", &path, Template::Test);
+        let info = insert_entry(INFO, "intro", &entry);
+        let list: ExerciseList = toml::from_str(&info).unwrap();
+        assert_eq!(
+            list.exercises[1].path,
+            Path::new("exercises/we\\ird \"topic\"/ex1.rs")
+        );
+    }
+
+    #[test]
+    fn test_names_have_to_be_identifiers() {
+        assert!(is_identifier("move_semantics2"));
+        assert!(is_identifier("_private"));
+        for name in ["", "../x", "2fast", "intro/intro3", "hello world", "héllo"] {
+            assert!(!is_identifier(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_parse_template() {
+        assert_eq!("test".parse(), Ok(Template::Test));
+        assert!("cargo".parse::<Template>().is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::ui::confirm;
+use rustlings::exercise::{Exercise, Topic};
+use rustlings::hint;
+use rustlings::pristine;
+use rustlings::progress::Progress;
+use rustlings::readme;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::str::FromStr;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Every command of the shell, as typed
+const COMMANDS: &[&str] = &[
+    "hint", "reset", "list", "next", "skip", "run", "goto", "verbose", "progress", "readme",
+    "clear", "quit", "help",
+];
+
+// A command typed in watch mode
+#[derive(PartialEq, Eq, Debug)]
+pub enum Command {
+    Hint,
+    Reset,
+    List,
+    // Move on to the next exercise without solving the current one
+    Next,
+    Run(String),
+    Goto(String),
+    Verbose(bool),
+    Progress,
+    Readme,
+    Clear,
+    Quit,
+    Help,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        match words[..] {
+            ["hint"] => Ok(Command::Hint),
+            ["reset"] => Ok(Command::Reset),
+            ["list"] => Ok(Command::List),
+            ["next" | "skip"] => Ok(Command::Next),
+            ["run", name] => Ok(Command::Run(name.to_string())),
+            ["goto", name] => Ok(Command::Goto(name.to_string())),
+            ["verbose", "on"] => Ok(Command::Verbose(true)),
+            ["verbose", "off"] => Ok(Command::Verbose(false)),
+            ["progress"] => Ok(Command::Progress),
+            ["readme"] => Ok(Command::Readme),
+            ["clear"] => Ok(Command::Clear),
+            ["quit"] => Ok(Command::Quit),
+            ["help"] => Ok(Command::Help),
+            ["run" | "goto", ..] => Err(format!("usage: {} <exercise name>", words[0])),
+            ["verbose", ..] => Err("usage: verbose on|off".to_string()),
+            [command, ..] if COMMANDS.contains(&command) => {
+                Err(format!("'{command}' doesn't take any argument"))
+            }
+            _ => Err(format!("unknown command: {}", input.trim())),
+        }
+    }
+}
+
+// Complete the word before the cursor: a command, or the argument of one.
+// Returns where the completed word starts, and the candidates.
+fn complete(line: &str, names: &[String]) -> (usize, Vec<String>) {
+    let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    let (before, word) = line.split_at(start);
+    let candidates: Vec<&str> = match before.split_whitespace().collect::<Vec<_>>()[..] {
+        [] => COMMANDS.to_vec(),
+        ["run" | "goto"] => names.iter().map(String::as_str).collect(),
+        ["verbose"] => vec!["on", "off"],
+        _ => Vec::new(),
+    };
+    let candidates = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(word))
+        .map(String::from)
+        .collect();
+    (start, candidates)
+}
+
+// Tab completion of commands and exercise names
+struct Completion {
+    names: Vec<String>,
+}
+
+impl Completer for Completion {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete(&line[..pos], &self.names))
+    }
+}
+
+impl Hinter for Completion {
+    type Hint = String;
+}
+
+impl Highlighter for Completion {}
+
+impl Validator for Completion {}
+
+impl Helper for Completion {}
+
+// The settings of the terminal before the shell took it over.
+// Watch mode can end while the shell is waiting for a command, with the
+// terminal in raw mode, so they are restored once this is dropped.
+pub struct Terminal {
+    #[cfg(unix)]
+    settings: Option<libc::termios>,
+}
+
+impl Terminal {
+    #[cfg(unix)]
+    fn save() -> Self {
+        let mut settings = std::mem::MaybeUninit::uninit();
+        // SAFETY: tcgetattr only writes to the termios it's given, and
+        // it's only read if tcgetattr succeeded.
+        let settings = unsafe {
+            (libc::tcgetattr(libc::STDIN_FILENO, settings.as_mut_ptr()) == 0)
+                .then(|| settings.assume_init())
+        };
+        Terminal { settings }
+    }
+
+    #[cfg(not(unix))]
+    fn save() -> Self {
+        Terminal {}
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(settings) = &self.settings {
+            // SAFETY: the settings were read from the same terminal
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, settings);
+            }
+        }
+    }
+}
+
+// Start reading commands in the background.
+// The commands that only show something are handled right away, while
+// those that change what watch mode is doing are sent to `requests`.
+pub fn spawn(
+    exercises: &[Exercise],
+    topics: &[Topic],
+    current_exercise: &Arc<Mutex<Option<Exercise>>>,
+    progress: &Arc<Mutex<Progress>>,
+    requests: Sender<Command>,
+) -> Terminal {
+    let terminal = Terminal::save();
+    let exercises = exercises.to_vec();
+    let topics = topics.to_vec();
+    let current_exercise = Arc::clone(current_exercise);
+    let progress = Arc::clone(progress);
+    println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
+    thread::spawn(move || {
+        let mut editor = match Editor::<Completion, DefaultHistory>::new() {
+            Ok(editor) => editor,
+            Err(error) => {
+                println!("error starting the watch mode shell: {error}");
+                return;
+            }
+        };
+        editor.set_helper(Some(Completion {
+            names: exercises.iter().map(|e| e.name.clone()).collect(),
+        }));
+
+        loop {
+            let input = match editor.readline("") {
+                Ok(input) => input,
+                // Ctrl-C is taken as a wish to leave
+                Err(ReadlineError::Interrupted) => {
+                    println!("Bye!");
+                    let _ = requests.send(Command::Quit);
+                    return;
+                }
+                // Nobody is typing anything anymore
+                Err(ReadlineError::Eof) => return,
+                Err(error) => {
+                    println!("error reading command: {error}");
+                    return;
+                }
+            };
+            if input.trim().is_empty() {
+                continue;
+            }
+            let _ = editor.add_history_entry(input.trim());
+
+            let command = match input.parse::<Command>() {
+                Ok(command) => command,
+                Err(error) => {
+                    println!("{error}");
+                    continue;
+                }
+            };
+            // Don't hold on to the exercise while waiting for the progress,
+            // which is locked while exercises are being verified
+            let current = current_exercise.lock().unwrap().clone();
+            match command {
+                Command::Hint => {
+                    if let Some(exercise) = &current {
+                        let mut progress = progress.lock().unwrap();
+                        match hint::reveal(exercise, &mut progress, false) {
+                            Ok(revealed) => {
+                                println!("{}", hint::render(exercise, revealed));
+                                if revealed < exercise.hints.len() {
+                                    println!();
+                                    println!("Type 'hint' again for a more detailed hint.");
+                                }
+                            }
+                            Err(e) => println!("Failed to save the progress file: {e}"),
+                        }
+                    }
+                }
+                Command::Reset => {
+                    if let Some(exercise) = &current {
+                        if confirm(&format!("Discard your changes to {exercise}?")) {
+                            let mut progress = progress.lock().unwrap();
+                            match pristine::reset(exercise, &mut progress) {
+                                Ok(true) => println!("Reset {exercise}"),
+                                Ok(false) => println!("There is no original version of {exercise}"),
+                                Err(e) => println!("Failed to reset {exercise}: {e}"),
+                            }
+                        }
+                    }
+                }
+                Command::List => {
+                    let progress = progress.lock().unwrap();
+                    let done = progress.done(&exercises);
+                    for exercise in &exercises {
+                        let marker = match &current {
+                            Some(current) if current.name == exercise.name => ">",
+                            _ => " ",
+                        };
+                        let status = progress.label(exercise, &exercises, &done);
+                        println!("{marker} {:<17}\t{status}", exercise.name);
+                    }
+                }
+                Command::Progress => {
+                    let progress = progress.lock().unwrap();
+                    println!("{}", progress.summary(&exercises));
+                }
+                Command::Readme => match &current {
+                    Some(exercise) => print_readme(exercise, &topics),
+                    None => println!("There is no current exercise"),
+                },
+                Command::Run(ref name) | Command::Goto(ref name)
+                    if !exercises.iter().any(|e| &e.name == name) =>
+                {
+                    println!("No exercise found for '{name}'!");
+                }
+                Command::Clear => println!("\x1B[2J\x1B[1;1H"),
+                Command::Help => print_help(),
+                Command::Quit => {
+                    println!("Bye!");
+                    let _ = requests.send(Command::Quit);
+                    return;
+                }
+                command => {
+                    if requests.send(command).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+    terminal
+}
+
+// Print the README introducing the exercise, like `rustlings readme` does
+fn print_readme(exercise: &Exercise, topics: &[Topic]) {
+    match readme::path(exercise, topics).map(|path| readme::load(&path)) {
+        Some(Ok(readme)) => println!("{readme}"),
+        _ => println!("There is no README for {exercise}"),
+    }
+}
+
+fn print_help() {
+    println!("Commands available to you in watch mode:");
+    println!("  hint           - prints the next hint of the current exercise");
+    println!("  reset          - restores the current exercise to its original state");
+    println!("  list           - lists the exercises and their status");
+    println!("  next, skip     - moves on to the next exercise without solving this one");
+    println!("  run <name>     - runs an exercise without leaving the current one");
+    println!("  goto <name>    - makes an exercise the current one");
+    println!("  verbose on|off - shows or hides the output of passing tests");
+    println!("  progress       - shows how many exercises you completed");
+    println!("  readme         - prints the README of the current exercise's topic");
+    println!("  clear          - clears the screen");
+    println!("  quit           - quits watch mode");
+    println!("  help           - displays this help message");
+    println!();
+    println!("Press Tab to complete commands and exercise names, and the arrow");
+    println!("keys to go through the commands you typed before.");
+    println!();
+    println!("Watch mode automatically re-evaluates the current exercise");
+    println!("when you edit a file's contents.")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!("hint".parse(), Ok(Command::Hint));
+        assert_eq!("  skip ".parse(), Ok(Command::Next));
+        assert_eq!("next".parse(), Ok(Command::Next));
+        assert_eq!("run intro1".parse(), Ok(Command::Run("intro1".into())));
+        assert_eq!("goto  if2".parse(), Ok(Command::Goto("if2".into())));
+        assert_eq!("verbose off".parse(), Ok(Command::Verbose(false)));
+        assert!("run".parse::<Command>().is_err());
+        assert!("verbose maybe".parse::<Command>().is_err());
+        assert!("hint me".parse::<Command>().is_err());
+        assert!("solve".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn test_complete_commands_and_names() {
+        let names = vec![
+            "intro1".to_string(),
+            "intro2".to_string(),
+            "if1".to_string(),
+        ];
+        assert_eq!(complete("he", &names), (0, vec!["help".to_string()]));
+        assert_eq!(
+            complete("goto intr", &names),
+            (5, vec!["intro1".to_string(), "intro2".to_string()])
+        );
+        assert_eq!(complete("verbose o", &names).1, vec!["on", "off"]);
+        assert_eq!(complete("hint i", &names).1, Vec::<String>::new());
+    }
+}
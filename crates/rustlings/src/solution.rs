@@ -0,0 +1,141 @@
+use crate::exercise::{project_files, Exercise, Mode};
+use console::{measure_text_width, style, truncate_str, Term};
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The width used when the terminal size is unknown, e.g. when piped
+const DEFAULT_WIDTH: usize = 160;
+// What separates the two columns of a side-by-side comparison
+const GUTTER: &str = " | ";
+
+/// Read the reference solution of the exercise.
+/// Returns None if the exercise has no solution.
+pub fn read_solution(exercise: &Exercise) -> io::Result<Option<String>> {
+    match read_sources(exercise, &exercise.solution_path()) {
+        Ok(solution) => Ok(Some(solution)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read the learner's version of the exercise
+pub fn read_exercise(exercise: &Exercise) -> io::Result<String> {
+    read_sources(exercise, &exercise.path)
+}
+
+// Read the file at the given path, or every file of the Cargo project there,
+// one after the other, each under a comment naming it
+fn read_sources(exercise: &Exercise, path: &Path) -> io::Result<String> {
+    let (Mode::Cargo | Mode::Exercism) = exercise.mode else {
+        return fs::read_to_string(path);
+    };
+    let mut sources = String::new();
+    for file in project_files(path)? {
+        let relative = file.strip_prefix(path).unwrap_or(&file);
+        sources.push_str(&format!("// {}\n", relative.display()));
+        sources.push_str(&fs::read_to_string(&file)?);
+    }
+    Ok(sources)
+}
+
+/// The width available for a side-by-side comparison
+pub fn terminal_width() -> usize {
+    let term = Term::stdout();
+    if term.is_term() {
+        term.size().1 as usize
+    } else {
+        DEFAULT_WIDTH
+    }
+}
+
+/// Lay out the learner's code and the solution next to each other,
+/// aligning the lines they have in common and highlighting the others
+pub fn side_by_side(mine: &str, solution: &str, width: usize) -> String {
+    let column = width.saturating_sub(GUTTER.len()) / 2;
+    let mut lines = vec![format!(
+        "{}{GUTTER}{}",
+        pad(&style("Your code").bold().to_string(), column),
+        style("Solution").bold()
+    )];
+
+    let diff = TextDiff::from_lines(mine, solution);
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    for change in diff.iter_all_changes() {
+        let line = change
+            .value()
+            .trim_end_matches(['\r', '\n'])
+            .replace('\t', "    ");
+        match change.tag() {
+            ChangeTag::Equal => {
+                flush(&mut lines, &mut left, &mut right, column);
+                lines.push(format!(
+                    "{}{GUTTER}{}",
+                    pad(&line, column),
+                    clip(&line, column)
+                ));
+            }
+            ChangeTag::Delete => left.push(style(line).red().to_string()),
+            ChangeTag::Insert => right.push(style(line).green().to_string()),
+        }
+    }
+    flush(&mut lines, &mut left, &mut right, column);
+
+    lines.join("\n")
+}
+
+// Emit the pending changed lines, pairing up removals and insertions
+fn flush(lines: &mut Vec<String>, left: &mut Vec<String>, right: &mut Vec<String>, column: usize) {
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).map(String::as_str).unwrap_or("");
+        let r = right.get(i).map(String::as_str).unwrap_or("");
+        lines.push(format!("{}{GUTTER}{}", pad(l, column), clip(r, column)));
+    }
+    left.clear();
+    right.clear();
+}
+
+fn clip(line: &str, column: usize) -> String {
+    truncate_str(line, column, "…").to_string()
+}
+
+fn pad(line: &str, column: usize) -> String {
+    let clipped = clip(line, column);
+    let padding = column.saturating_sub(measure_text_width(&clipped));
+    format!("{clipped}{}", " ".repeat(padding))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_side_by_side_aligns_common_lines() {
+        let mine = "fn main() {\n    let x;\n}\n";
+        let solution = "fn main() {\n    let x = 5;\n    println!(\"{x}\");\n}\n";
+        let lines: Vec<String> = side_by_side(mine, solution, 43)
+            .lines()
+            .map(|line| console::strip_ansi_codes(line).to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "Your code            | Solution",
+                "fn main() {          | fn main() {",
+                "    let x;           |     let x = 5;",
+                "                     |     println!(\"{x}\");",
+                "}                    | }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_side_by_side_clips_long_lines() {
+        let lines: Vec<String> = side_by_side("a very long line indeed\n", "short\n", 23)
+            .lines()
+            .map(|line| console::strip_ansi_codes(line).to_string())
+            .collect();
+        assert_eq!(lines[1], "a very lo… | short");
+    }
+}
@@ -0,0 +1,240 @@
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// How often a running child is checked for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// The result of a supervised run of a binary
+pub struct Supervised {
+    // The exit status and everything the binary printed
+    pub output: Output,
+    // Whether or not the binary was killed for running too long
+    pub timed_out: bool,
+    // The CPU time the binary used, where the platform reports it
+    pub cpu_time: Option<Duration>,
+}
+
+// Run the command to completion, collecting its output like `Command::output`,
+// but kill it once it has been running for longer than the timeout.
+// The input, if any, is fed to the standard input of the command.
+// On Unix the child is put into its own process group, and the whole group
+// is killed once the child is done, before the child is reaped, so that any
+// process it spawned is cleaned up as well.
+pub fn output_with_timeout(
+    command: &mut Command,
+    input: Option<&[u8]>,
+    timeout: Duration,
+) -> io::Result<Supervised> {
+    let stdin = match input {
+        Some(_) => Stdio::piped(),
+        None => Stdio::null(),
+    };
+    command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn()?;
+    let stdin = write_all(child.stdin.take(), input.map(<[u8]>::to_vec));
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let mut timed_out = false;
+    while !has_exited(&mut child)? {
+        if Instant::now() >= deadline {
+            timed_out = true;
+            break;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    // Leftover grandchildren would keep the pipes open forever
+    kill_tree(&mut child);
+    let (status, cpu_time) = reap(&mut child)?;
+    // The pipe is broken by now if the child didn't read all of its input
+    let _ = stdin.join();
+
+    Ok(Supervised {
+        output: Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        },
+        timed_out,
+        cpu_time,
+    })
+}
+
+// Feed the input to a pipe on a separate thread, so that a child that
+// doesn't read its input can't block us. The pipe is closed once all of the
+// input is written, so that the child sees the end of its input.
+fn write_all(pipe: Option<impl Write + Send + 'static>, input: Option<Vec<u8>>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        if let (Some(mut pipe), Some(input)) = (pipe, input) {
+            let _ = pipe.write_all(&input);
+        }
+    })
+}
+
+// Drain a pipe on a separate thread, so that a chatty child can't block
+// on a full pipe while we're waiting for it
+fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+// Check whether the child exited, without reaping it: until it's reaped,
+// its pid and so the id of its process group can't be recycled
+#[cfg(unix)]
+fn has_exited(child: &mut Child) -> io::Result<bool> {
+    let pid = libc::id_t::from(child.id());
+    // SAFETY: siginfo_t is plain old data, for which all zeroes is valid
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    // SAFETY: info is a valid siginfo_t for waitid to write to
+    let result = unsafe {
+        libc::waitid(
+            libc::P_PID,
+            pid,
+            &mut info,
+            libc::WEXITED | libc::WNOHANG | libc::WNOWAIT,
+        )
+    };
+    if result == -1 {
+        let error = io::Error::last_os_error();
+        return match error.kind() {
+            io::ErrorKind::Interrupted => Ok(false),
+            _ => Err(error),
+        };
+    }
+    // With WNOHANG, the pid is left to zero while the child is running
+    // SAFETY: waitid succeeded, so info was filled in
+    Ok(unsafe { info.si_pid() } != 0)
+}
+
+#[cfg(not(unix))]
+fn has_exited(child: &mut Child) -> io::Result<bool> {
+    Ok(child.try_wait()?.is_some())
+}
+
+#[cfg(unix)]
+fn kill_tree(child: &mut Child) {
+    // The child is the leader of its process group,
+    // so the group id is the child's pid
+    if let Ok(pgid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: kill has no memory safety requirements. The group can't
+        // have been recycled since the child, its leader, isn't reaped yet.
+        unsafe {
+            libc::kill(-pgid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_tree(child: &mut Child) {
+    let _ = child.kill();
+}
+
+// Wait for the child, along with the CPU time it used
+#[cfg(unix)]
+fn reap(child: &mut Child) -> io::Result<(ExitStatus, Option<Duration>)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let pid = libc::pid_t::try_from(child.id()).map_err(io::Error::other)?;
+    let mut status = 0;
+    // SAFETY: rusage is plain old data, for which all zeroes is valid
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: status and usage are valid for wait4 to write to
+        if unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } != -1 {
+            break;
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+    let time = |time: libc::timeval| {
+        Duration::from_secs(time.tv_sec.try_into().unwrap_or_default())
+            + Duration::from_micros(time.tv_usec.try_into().unwrap_or_default())
+    };
+    let cpu_time = time(usage.ru_utime) + time(usage.ru_stime);
+    Ok((ExitStatus::from_raw(status), Some(cpu_time)))
+}
+
+#[cfg(not(unix))]
+fn reap(child: &mut Child) -> io::Result<(ExitStatus, Option<Duration>)> {
+    Ok((child.wait()?, None))
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fast_command_completes() {
+        let supervised = output_with_timeout(
+            Command::new("echo").arg("hello"),
+            None,
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert!(!supervised.timed_out);
+        assert!(supervised.output.status.success());
+        assert_eq!(supervised.output.stdout, b"hello\n");
+    }
+
+    #[test]
+    fn test_slow_command_is_killed_with_its_children() {
+        let start = Instant::now();
+        let supervised = output_with_timeout(
+            Command::new("sh").args(["-c", "sleep 30 & sleep 30"]),
+            None,
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        assert!(supervised.timed_out);
+        assert!(!supervised.output.status.success());
+        assert!(supervised.cpu_time.unwrap() < Duration::from_secs(1));
+        // The backgrounded sleep would hold stdout open if it survived
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_children_are_killed_once_the_command_exits() {
+        let start = Instant::now();
+        let supervised = output_with_timeout(
+            Command::new("sh").args(["-c", "sleep 30 & echo done"]),
+            None,
+            Duration::from_secs(20),
+        )
+        .unwrap();
+        assert!(!supervised.timed_out);
+        assert!(supervised.output.status.success());
+        assert_eq!(supervised.output.stdout, b"done\n");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_input_is_fed_to_stdin() {
+        let supervised = output_with_timeout(
+            &mut Command::new("cat"),
+            Some(b"hello\n"),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert!(supervised.output.status.success());
+        assert_eq!(supervised.output.stdout, b"hello\n");
+    }
+}
@@ -0,0 +1,621 @@
+use crate::verify::{is_done, summarize, VerifyOptions};
+use crate::watch::{
+    edited_file, pending, pending_after_edit, pending_from, skipped_message, watch_exercises,
+    WatchStatus,
+};
+use ansi_to_tui::IntoText;
+use console::style;
+use notify::{DebouncedEvent, RecommendedWatcher, Watcher};
+use ratatui::backend::Backend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame, Terminal};
+use rustlings::exercise::{Exercise, State, Topic};
+use rustlings::hint;
+use rustlings::outcome::{evaluate, evaluate_all, Outcome};
+use rustlings::pristine;
+use rustlings::progress::{self, Progress, Verification};
+use rustlings::readme;
+use std::collections::HashSet;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+// The key bindings, as shown at the bottom of the screen
+const KEYS: &str = "↑↓ select  enter goto  r run  n next  h hint  i readme  v verbose  x reset  PgUp/PgDn scroll  q quit";
+
+// Whether or not the terminal can show the full-screen interface
+pub fn is_supported() -> bool {
+    io::stdout().is_terminal() && env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+// Watch mode, in a full-screen interface.
+// It works like the plain one, with the shell commands bound to keys.
+pub fn watch(
+    exercises: &[Exercise],
+    topics: &[Topic],
+    progress: Progress,
+    options: VerifyOptions,
+) -> notify::Result<WatchStatus> {
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(2))?;
+    watch_exercises(&mut watcher, exercises)?;
+
+    let mut terminal = ratatui::try_init()?;
+    let mut app = App::new(exercises, topics, progress, options);
+    let status = app.run(&mut terminal, &rx);
+    ratatui::try_restore()?;
+    Ok(status?)
+}
+
+// Everything the full-screen interface shows
+struct App<'a> {
+    exercises: &'a [Exercise],
+    topics: &'a [Topic],
+    progress: Progress,
+    options: VerifyOptions,
+    // The exercise the end user has to work on, if any
+    current: Option<&'a Exercise>,
+    // The exercises the end user moved past without solving them
+    skipped: HashSet<&'a str>,
+    // The exercise highlighted in the sidebar
+    selected: usize,
+    sidebar: ListState,
+    // What the output pane shows, and how far it's scrolled down
+    title: String,
+    output: Text<'static>,
+    scroll: u16,
+    // The hints revealed for the current exercise, if any
+    hints: Option<Text<'static>>,
+    // What happened after the last key was pressed
+    message: String,
+    // Whether or not the next key confirms resetting the current exercise
+    confirming_reset: bool,
+}
+
+impl<'a> App<'a> {
+    fn new(
+        exercises: &'a [Exercise],
+        topics: &'a [Topic],
+        progress: Progress,
+        options: VerifyOptions,
+    ) -> Self {
+        App {
+            exercises,
+            topics,
+            progress,
+            options,
+            current: None,
+            skipped: HashSet::new(),
+            selected: 0,
+            sidebar: ListState::default(),
+            title: String::new(),
+            output: Text::default(),
+            scroll: 0,
+            hints: None,
+            message: String::new(),
+            confirming_reset: false,
+        }
+    }
+
+    // Verify the pending exercises, then react to keys and edited files
+    // until the end user quits or every exercise is done
+    fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        events: &Receiver<DebouncedEvent>,
+    ) -> io::Result<WatchStatus> {
+        let pending_exercises = pending(self.exercises, &self.progress, &self.skipped);
+        if self.verify(terminal, pending_exercises)? {
+            return Ok(WatchStatus::Finished);
+        }
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(Duration::from_millis(200))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match self.on_key(terminal, key)? {
+                            Some(status) => return Ok(status),
+                            None => continue,
+                        }
+                    }
+                }
+            }
+            for filepath in events.try_iter().filter_map(edited_file) {
+                let pending_exercises = pending_after_edit(
+                    self.exercises,
+                    &self.progress,
+                    &filepath,
+                    &mut self.skipped,
+                );
+                if self.verify(terminal, pending_exercises)? {
+                    return Ok(WatchStatus::Finished);
+                }
+            }
+        }
+    }
+
+    // Act on a key, returning how watch mode ends if it does
+    fn on_key<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        key: KeyEvent,
+    ) -> io::Result<Option<WatchStatus>> {
+        if self.confirming_reset {
+            self.confirming_reset = false;
+            self.message = match (self.current, key.code) {
+                (Some(exercise), KeyCode::Char('y')) => {
+                    match pristine::reset(exercise, &mut self.progress) {
+                        Ok(true) => format!("Reset {exercise}"),
+                        Ok(false) => format!("There is no original version of {exercise}"),
+                        Err(e) => format!("Failed to reset {exercise}: {e}"),
+                    }
+                }
+                _ => String::new(),
+            };
+            return Ok(None);
+        }
+
+        let exercises = self.exercises;
+        let selected = &exercises[self.selected];
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(WatchStatus::Unfinished)),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(Some(WatchStatus::Unfinished))
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.exercises.len() - 1)
+            }
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Enter | KeyCode::Char('g') => {
+                let pending_exercises =
+                    pending_from(self.exercises, &self.progress, selected, &mut self.skipped);
+                if self.verify(terminal, pending_exercises)? {
+                    return Ok(Some(WatchStatus::Finished));
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('s') => match self.current {
+                Some(current) => {
+                    self.skipped.insert(&current.name);
+                    let pending_exercises = pending(self.exercises, &self.progress, &self.skipped);
+                    if self.verify(terminal, pending_exercises)? {
+                        return Ok(Some(WatchStatus::Finished));
+                    }
+                }
+                None => self.message = String::from("There is no exercise to skip"),
+            },
+            KeyCode::Char('r') => {
+                self.message = format!("Running {selected}...");
+                terminal.draw(|frame| self.draw(frame))?;
+                let (outcome, _) = evaluate(selected, self.options.sandbox, || {});
+                self.show(selected, &outcome);
+                self.message.clear();
+            }
+            KeyCode::Char('h') => match self.current {
+                Some(current) => match hint::reveal(current, &mut self.progress, false) {
+                    Ok(revealed) => {
+                        self.hints = Some(ansi_text(&hint::render(current, revealed)));
+                        self.message = match revealed < current.hints.len() {
+                            true => String::from("Press h again for a more detailed hint"),
+                            false => String::new(),
+                        };
+                    }
+                    Err(e) => self.message = format!("Failed to save the progress file: {e}"),
+                },
+                None => self.message = String::from("There is no current exercise"),
+            },
+            KeyCode::Char('i') => {
+                let path = readme::path(selected, self.topics);
+                match path.as_ref().map(|path| (path, readme::load(path))) {
+                    Some((path, Ok(readme))) => {
+                        self.title = path.display().to_string();
+                        self.output = ansi_text(&readme);
+                        self.scroll = 0;
+                    }
+                    _ => self.message = format!("There is no README for {selected}"),
+                }
+            }
+            KeyCode::Char('v') => {
+                self.options.verbose = !self.options.verbose;
+                self.message = match self.options.verbose {
+                    true => String::from("The output of passing tests will be shown"),
+                    false => String::from("The output of passing tests will be hidden"),
+                };
+            }
+            KeyCode::Char('x') => match self.current {
+                Some(current) => {
+                    self.confirming_reset = true;
+                    self.message = format!("Discard your changes to {current}? Press y to confirm");
+                }
+                None => self.message = String::from("There is no current exercise"),
+            },
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    // Verify the pending exercises like `verify` does, on as many jobs,
+    // stopping at the first one that isn't done unless told to keep going.
+    // The first exercise that isn't done becomes the current one.
+    // Returns whether or not every exercise is done.
+    fn verify<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        pending_exercises: Vec<&'a Exercise>,
+    ) -> io::Result<bool> {
+        let options = self.options;
+        let mut first_failure = None;
+        let mut drawn = Ok(());
+        let mut checking = |app: &mut Self, index: usize| {
+            if let Some(exercise) = pending_exercises.get(index) {
+                app.message = format!("Checking {exercise}...");
+                if drawn.is_ok() {
+                    drawn = terminal.draw(|frame| app.draw(frame)).map(|_| ());
+                }
+            }
+        };
+        checking(self, 0);
+        let mut handled = 0;
+        evaluate_all(
+            &pending_exercises,
+            options.jobs,
+            options.sandbox,
+            |exercise, outcome, _| {
+                handled += 1;
+                let done = is_done(exercise, &outcome);
+                if done
+                    && self
+                        .progress
+                        .mark_done(exercise)
+                        .and_then(|_| self.progress.save())
+                        .is_err()
+                {
+                    self.message = format!("Failed to record the progress of {exercise}");
+                }
+                if !done && first_failure.is_none() {
+                    first_failure = Some((exercise, outcome));
+                }
+                let carry_on = first_failure.is_none() || options.keep_going;
+                if carry_on {
+                    checking(self, handled);
+                }
+                carry_on
+            },
+        );
+        drawn?;
+
+        if let Some((exercise, outcome)) = first_failure {
+            self.show(exercise, &outcome);
+            self.current = Some(exercise);
+            self.select(exercise);
+            let revealed = self.progress.hints_revealed(exercise);
+            self.hints = (revealed > 0).then(|| ansi_text(&hint::render(exercise, revealed)));
+            self.message.clear();
+            return Ok(false);
+        }
+
+        self.current = None;
+        self.hints = None;
+        self.message.clear();
+        if self.skipped.is_empty() {
+            return Ok(true);
+        }
+        self.title = String::from("Skipped exercises");
+        self.output = Text::from(skipped_message(self.exercises, &self.skipped));
+        self.scroll = 0;
+        Ok(false)
+    }
+
+    // Show the outcome of the exercise in the output pane
+    fn show(&mut self, exercise: &Exercise, outcome: &Outcome) {
+        let summary = summarize(exercise, outcome, self.options.verbose);
+        let mut output = summary.details;
+        if summary.passed {
+            output.push_str(&keep_working(exercise));
+        }
+        self.title = summary.headline;
+        self.output = ansi_text(&output);
+        self.scroll = 0;
+    }
+
+    fn select(&mut self, exercise: &Exercise) {
+        if let Some(index) = self.exercises.iter().position(|e| e.name == exercise.name) {
+            self.selected = index;
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let sidebar_width = self
+            .exercises
+            .iter()
+            .map(|e| e.name.len())
+            .max()
+            .unwrap_or(0)
+            + 8;
+        let [sidebar, main] =
+            Layout::horizontal([Constraint::Length(sidebar_width as u16), Constraint::Min(0)])
+                .areas(body);
+
+        // Which exercises are done is only worked out once per render
+        let done = self.progress.done(self.exercises);
+        let gauge = Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title(" Rustlings "))
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio(done.len() as f64 / self.exercises.len().max(1) as f64)
+            .label(format!("{}/{}", done.len(), self.exercises.len()));
+        frame.render_widget(gauge, header);
+
+        let (items, selected_row) = self.sidebar_items(&done);
+        self.sidebar.select(Some(selected_row));
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Exercises "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, sidebar, &mut self.sidebar);
+
+        let (output_area, hints_area) = match &self.hints {
+            Some(hints) => {
+                let height = (hints.height() as u16 + 2).min(main.height / 2);
+                let [output_area, hints_area] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(height)]).areas(main);
+                (output_area, Some(hints_area))
+            }
+            None => (main, None),
+        };
+        let output = Paragraph::new(self.output.clone())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", self.title)),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(output, output_area);
+        if let (Some(hints), Some(hints_area)) = (&self.hints, hints_area) {
+            let hints = Paragraph::new(hints.clone())
+                .block(Block::default().borders(Borders::ALL).title(" Hints "))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(hints, hints_area);
+        }
+
+        let footer_text = Text::from(vec![
+            Line::from(Span::styled(
+                self.message.clone(),
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(Span::styled(KEYS, Style::default().fg(Color::DarkGray))),
+        ]);
+        frame.render_widget(Paragraph::new(footer_text), footer);
+    }
+
+    // The exercises grouped by topic, each with an icon for its status.
+    // Also returns the row of the selected exercise.
+    fn sidebar_items(&self, done: &HashSet<&str>) -> (Vec<ListItem<'static>>, usize) {
+        let mut items = Vec::new();
+        let mut selected_row = 0;
+        let mut topic = None;
+        for (index, exercise) in self.exercises.iter().enumerate() {
+            if exercise.topic() != topic {
+                topic = exercise.topic();
+                items.push(ListItem::new(Span::styled(
+                    topic.unwrap_or("other").to_string(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )));
+            }
+            let (icon, color) = if self.current.is_some_and(|e| e.name == exercise.name) {
+                ("✗", Color::Red)
+            } else if self.skipped.contains(exercise.name.as_str()) {
+                ("»", Color::DarkGray)
+            } else if !done.contains(exercise.name.as_str())
+                && !progress::is_unlocked(exercise, self.exercises, done)
+            {
+                ("-", Color::DarkGray)
+            } else {
+                match self.progress.verification(exercise) {
+                    Verification::Verified => ("✓", Color::Green),
+                    Verification::Outdated => ("~", Color::Yellow),
+                    Verification::Unverified => ("·", Color::Reset),
+                }
+            };
+            if index == self.selected {
+                selected_row = items.len();
+            }
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!(" {icon} "), Style::default().fg(color)),
+                Span::raw(exercise.name.clone()),
+            ])));
+        }
+        (items, selected_row)
+    }
+}
+
+// Parse the colors of compiler and test output
+fn ansi_text(text: &str) -> Text<'static> {
+    text.into_text()
+        .unwrap_or_else(|_| Text::from(console::strip_ansi_codes(text).into_owned()))
+}
+
+// What's left to do in an exercise that passes, if its marker is still there
+fn keep_working(exercise: &Exercise) -> String {
+    let Ok(State::Pending(context)) = exercise.state() else {
+        return String::new();
+    };
+    let mut text = format!(
+        "\nYou can keep working on this exercise,\nor jump into the next one by removing the {} comment:\n\n",
+        style("`I AM NOT DONE`").bold()
+    );
+    for line in context {
+        let line_text = match line.important {
+            true => style(line.line).bold().to_string(),
+            false => line.line,
+        };
+        text.push_str(&format!("{:>2} | {line_text}\n", line.number));
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use std::fs;
+    use std::process;
+
+    fn exercise(name: &str, topic: &str, path: &str) -> Exercise {
+        Exercise {
+            name: name.into(),
+            path: path.into(),
+            topic: Some(topic.into()),
+            hints: vec!["First".into(), "Second".into()],
+            ..Default::default()
+        }
+    }
+
+    fn exercises() -> Vec<Exercise> {
+        vec![
+            exercise("done", "basics", "tests/fixture/state/finished_exercise.rs"),
+            exercise(
+                "current",
+                "basics",
+                "tests/fixture/state/pending_exercise.rs",
+            ),
+            exercise(
+                "skipped",
+                "basics",
+                "tests/fixture/state/pending_exercise.rs",
+            ),
+            Exercise {
+                requires: vec!["basics".into()],
+                ..exercise(
+                    "locked",
+                    "advanced",
+                    "tests/fixture/state/pending_exercise.rs",
+                )
+            },
+        ]
+    }
+
+    // A progress store that's only saved to a scratch file
+    fn progress(name: &str) -> Progress {
+        let path = std::env::temp_dir().join(format!("rustlings_tui_{}_{name}", process::id()));
+        Progress::load(path).unwrap()
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> Option<WatchStatus> {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        app.on_key(&mut terminal, KeyEvent::from(code)).unwrap()
+    }
+
+    #[test]
+    fn test_reset_has_to_be_confirmed() {
+        let exercises = exercises();
+        let mut app = App::new(
+            &exercises,
+            &[],
+            progress("reset"),
+            VerifyOptions::new(false),
+        );
+        press(&mut app, KeyCode::Char('x'));
+        assert!(!app.confirming_reset);
+        assert_eq!(app.message, "There is no current exercise");
+
+        app.current = Some(&exercises[1]);
+        press(&mut app, KeyCode::Char('x'));
+        assert!(app.confirming_reset);
+        // Any other key than y cancels, without doing anything else
+        assert!(press(&mut app, KeyCode::Char('q')).is_none());
+        assert!(!app.confirming_reset);
+        assert!(app.message.is_empty());
+
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Char('y'));
+        assert!(!app.confirming_reset);
+        assert_eq!(
+            app.message,
+            "There is no original version of tests/fixture/state/pending_exercise.rs"
+        );
+    }
+
+    #[test]
+    fn test_hints_and_verbosity() {
+        let exercises = exercises();
+        let progress_path =
+            std::env::temp_dir().join(format!("rustlings_tui_{}_hints", process::id()));
+        let mut app = App::new(
+            &exercises,
+            &[],
+            progress("hints"),
+            VerifyOptions::new(false),
+        );
+        press(&mut app, KeyCode::Char('h'));
+        assert!(app.hints.is_none());
+
+        app.current = Some(&exercises[1]);
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.hints.as_ref().unwrap().height(), 1);
+        assert_eq!(app.message, "Press h again for a more detailed hint");
+        press(&mut app, KeyCode::Char('h'));
+        assert!(app.hints.as_ref().unwrap().height() > 1);
+        assert!(app.message.is_empty());
+        fs::remove_file(progress_path).unwrap();
+
+        press(&mut app, KeyCode::Char('v'));
+        assert!(app.options.verbose);
+        press(&mut app, KeyCode::Char('v'));
+        assert!(!app.options.verbose);
+        assert!(matches!(
+            press(&mut app, KeyCode::Esc),
+            Some(WatchStatus::Unfinished)
+        ));
+    }
+
+    #[test]
+    fn test_sidebar_items() {
+        let exercises = exercises();
+        let mut progress = progress("sidebar");
+        progress.mark_done(&exercises[0]).unwrap();
+        let mut app = App::new(&exercises, &[], progress, VerifyOptions::new(false));
+        app.current = Some(&exercises[1]);
+        app.skipped.insert("skipped");
+        app.selected = 3;
+
+        let topic = |name: &str| {
+            ListItem::new(Span::styled(
+                name.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ))
+        };
+        let row = |icon: &str, color: Color, name: &str| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {icon} "), Style::default().fg(color)),
+                Span::raw(name.to_string()),
+            ]))
+        };
+        let done = app.progress.done(&exercises);
+        let (items, selected_row) = app.sidebar_items(&done);
+        assert_eq!(
+            items,
+            [
+                topic("basics"),
+                row("✓", Color::Green, "done"),
+                row("✗", Color::Red, "current"),
+                row("»", Color::DarkGray, "skipped"),
+                topic("advanced"),
+                row("-", Color::DarkGray, "locked"),
+            ]
+        );
+        // The topics have rows of their own
+        assert_eq!(selected_row, 5);
+    }
+}
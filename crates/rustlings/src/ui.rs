@@ -1,25 +1,33 @@
+use rustlings::format::Format;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Whether or not stdout is reserved for JSON records, in which case
+// warnings and errors go to stderr
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
 macro_rules! warn {
-    ($fmt:literal, $ex:expr) => {{
+    ($fmt:literal, $($ex:expr),+) => {{
         use console::{style, Emoji};
         use std::env;
-        let formatstr = format!($fmt, $ex);
+        let formatstr = format!($fmt, $($ex),+);
         if env::var("NO_EMOJI").is_ok() {
-            println!("{} {}", style("!").red(), style(formatstr).red());
+            $crate::ui::message(&format!("{} {}", style("!").red(), style(formatstr).red()));
         } else {
-            println!(
+            $crate::ui::message(&format!(
                 "{} {}",
                 style(Emoji("⚠️ ", "!")).red(),
                 style(formatstr).red()
-            );
+            ));
         }
     }};
 }
 
 macro_rules! success {
-    ($fmt:literal, $ex:expr) => {{
+    ($fmt:literal, $($ex:expr),+) => {{
         use console::{style, Emoji};
         use std::env;
-        let formatstr = format!($fmt, $ex);
+        let formatstr = format!($fmt, $($ex),+);
         if env::var("NO_EMOJI").is_ok() {
             println!("{} {}", style("✓").green(), style(formatstr).green());
         } else {
@@ -31,3 +39,26 @@ macro_rules! success {
         }
     }};
 }
+
+// Set the output format of the whole run
+pub fn set_format(format: Format) {
+    JSON_OUTPUT.store(format == Format::Json, Ordering::Relaxed);
+}
+
+// Print a warning or an error for the end user, out of the way of JSON records
+pub fn message(message: &str) {
+    if JSON_OUTPUT.load(Ordering::Relaxed) {
+        eprintln!("{message}");
+    } else {
+        println!("{message}");
+    }
+}
+
+// Ask the end user a yes/no question, defaulting to no
+pub fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
@@ -1,29 +1,116 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
 use console::style;
 use indicatif::ProgressBar;
+use rustlings::diff;
+use rustlings::error::Error;
+use rustlings::exercise::{Exercise, ExerciseOutput, ExpectedOutput, Mode, State};
+use rustlings::format::{emit, ExerciseRecord, Format, Status};
+use rustlings::outcome::{evaluate, evaluate_all, Outcome};
+use rustlings::progress::{Progress, Verification};
+use rustlings::sandbox::{Sandbox, Violation};
 use std::env;
 use std::time::Duration;
 
+// Options controlling how a set of exercises is verified
+#[derive(Copy, Clone, Debug)]
+pub struct VerifyOptions {
+    // Whether or not to display the test harness outputs
+    pub verbose: bool,
+    // The number of exercises compiled and run concurrently
+    pub jobs: usize,
+    // Whether or not to carry on with the remaining exercises after a failure
+    pub keep_going: bool,
+    // How the outcome of every exercise is reported
+    pub format: Format,
+    // The limits learner binaries are confined to, if any
+    pub sandbox: Option<Sandbox>,
+}
+
+impl VerifyOptions {
+    pub fn new(verbose: bool) -> Self {
+        VerifyOptions {
+            verbose,
+            jobs: 1,
+            keep_going: false,
+            format: Format::Text,
+            sandbox: None,
+        }
+    }
+}
+
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
-// Any such failures will be reported to the end user.
-// If the Exercise being verified is a test, the verbose boolean
+// Any such failures will be reported to the end user, in the order
+// the exercises were provided, even if they were checked concurrently.
+// If the Exercise being verified is a test, the verbose option
 // determines whether or not the test harness outputs are displayed.
+// Every exercise that is verified as done is recorded in the progress store.
+// The first exercise that isn't done is returned along with the error.
 pub fn verify<'a>(
     start_at: impl IntoIterator<Item = &'a Exercise>,
-    verbose: bool,
-) -> Result<(), &'a Exercise> {
-    for exercise in start_at {
-        let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose),
-            Mode::Compile => compile_and_run_interactively(exercise),
-            Mode::Clippy => compile_only(exercise),
+    progress: &mut Progress,
+    options: VerifyOptions,
+) -> Result<(), (&'a Exercise, Error)> {
+    let exercises: Vec<&'a Exercise> = start_at.into_iter().collect();
+    let mut first_failure = None;
+    let mut handle = |exercise: &'a Exercise, outcome: Outcome, duration: Duration| {
+        let done = match options.format {
+            Format::Text => report(exercise, &outcome, RunMode::Interactive, options.verbose),
+            // Nobody can be prompted, so the marker alone decides
+            Format::Json => is_done(exercise, &outcome),
+        };
+        if done
+            && progress
+                .mark_done(exercise)
+                .and_then(|_| progress.save())
+                .is_err()
+        {
+            warn!("Failed to record the progress of {}", exercise);
+        }
+        if let Format::Json = options.format {
+            emit_record(
+                exercise,
+                &outcome,
+                duration,
+                progress.verification(exercise),
+            );
+        }
+        if !done && first_failure.is_none() {
+            first_failure = Some((exercise, outcome.into_error(exercise)));
+        }
+        done || options.keep_going
+    };
+
+    if options.jobs > 1 {
+        let progress_bar = match options.format {
+            Format::Text => ProgressBar::new(exercises.len() as u64),
+            Format::Json => ProgressBar::hidden(),
         };
-        if !compile_result.unwrap_or(false) {
-            return Err(exercise);
+        progress_bar.set_message(format!("Verifying with {} jobs...", options.jobs));
+        progress_bar.enable_steady_tick(Duration::from_millis(100));
+        evaluate_all(
+            &exercises,
+            options.jobs,
+            options.sandbox,
+            |exercise, outcome, duration| {
+                progress_bar.inc(1);
+                progress_bar.suspend(|| handle(exercise, outcome, duration))
+            },
+        );
+        progress_bar.finish_and_clear();
+    } else {
+        for exercise in exercises {
+            let (outcome, duration) =
+                evaluate_with_spinner(exercise, options.format, options.sandbox);
+            if !handle(exercise, outcome, duration) {
+                break;
+            }
         }
     }
-    Ok(())
+
+    match first_failure {
+        Some(failure) => Err(failure),
+        None => Ok(()),
+    }
 }
 
 enum RunMode {
@@ -32,111 +119,295 @@ enum RunMode {
 }
 
 // Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose)?;
-    Ok(())
+pub fn test(exercise: &Exercise, options: VerifyOptions) -> Result<(), Error> {
+    let (outcome, _) = evaluate_with_spinner(exercise, Format::Text, options.sandbox);
+    if report(exercise, &outcome, RunMode::NonInteractive, options.verbose) {
+        Ok(())
+    } else {
+        Err(outcome.into_error(exercise))
+    }
 }
 
-// Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
-
-    let _ = compile(exercise, &progress_bar)?;
-    progress_bar.finish_and_clear();
-
-    success!("Successfully compiled {}!", exercise);
-    Ok(prompt_for_completion(exercise, None))
+// Compile and run the given Exercise, emitting its outcome as JSON
+// instead of reporting it to the end user
+pub fn check(
+    exercise: &Exercise,
+    state: Verification,
+    options: VerifyOptions,
+) -> Result<(), Error> {
+    let (outcome, duration) = evaluate(exercise, options.sandbox, || {});
+    emit_record(exercise, &outcome, duration, state);
+    match outcome {
+        Outcome::Success(_) => Ok(()),
+        outcome => Err(outcome.into_error(exercise)),
+    }
 }
 
-// Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise) -> Result<bool, ()> {
+fn spinner(exercise: &Exercise, format: Format) -> ProgressBar {
+    if let Format::Json = format {
+        return ProgressBar::hidden();
+    }
     let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
+    let action = match exercise.mode {
+        Mode::Test | Mode::Cargo | Mode::Exercism => "Testing",
+        Mode::Compile | Mode::Clippy | Mode::CompileFail => "Compiling",
+    };
+    progress_bar.set_message(format!("{action} {exercise}..."));
     progress_bar.enable_steady_tick(Duration::from_millis(100));
+    progress_bar
+}
 
-    let compilation = compile(exercise, &progress_bar)?;
-
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
+// Evaluate the given Exercise behind a spinner telling what's going on
+fn evaluate_with_spinner(
+    exercise: &Exercise,
+    format: Format,
+    sandbox: Option<Sandbox>,
+) -> (Outcome, Duration) {
+    let progress_bar = spinner(exercise, format);
+    let evaluation = evaluate(exercise, sandbox, || {
+        if let Mode::Compile = exercise.mode {
+            progress_bar.set_message(format!("Running {exercise}..."));
+        }
+    });
     progress_bar.finish_and_clear();
+    evaluation
+}
 
-    let output = match result {
-        Ok(output) => output,
-        Err(output) => {
-            warn!("Ran {} with errors", exercise);
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
-            return Err(());
+// Print the machine-readable record of an evaluated Exercise
+fn emit_record(exercise: &Exercise, outcome: &Outcome, duration: Duration, state: Verification) {
+    let mut record = ExerciseRecord::new(exercise, state);
+    let (status, output) = match outcome {
+        Outcome::CompileFailure(output) => (Status::CompileFailure, Some(output)),
+        Outcome::NotRejected => (Status::NotRejected, None),
+        Outcome::WrongError(output, _) => (Status::WrongError, Some(output)),
+        Outcome::RunFailure(output) => (Status::RunFailure, Some(output)),
+        Outcome::WrongOutput(output) => (Status::WrongOutput, Some(output)),
+        Outcome::Timeout(output) => (Status::Timeout, Some(output)),
+        Outcome::LimitExceeded(output, violation) => {
+            record.violation = Some(*violation);
+            (Status::LimitExceeded, Some(output))
+        }
+        Outcome::Success(output) => (Status::Success, output.as_ref()),
+        Outcome::Error(error) => {
+            record.stderr = Some(error.to_string());
+            (Status::Error, None)
         }
     };
+    record.status = Some(status);
+    // The compiler output is colored for humans
+    if let Some(output) = output {
+        record.stdout = Some(console::strip_ansi_codes(&output.stdout).into_owned());
+        record.stderr = Some(console::strip_ansi_codes(&output.stderr).into_owned());
+        if !output.diagnostics.is_empty() {
+            record.diagnostics = Some(&output.diagnostics);
+        }
+    }
+    record.duration_ms = Some(duration.as_millis());
+    // There's nobody left to tell if stdout is gone
+    let _ = emit(&record);
+}
 
-    success!("Successfully ran {}!", exercise);
+// Whether or not the outcome of the given Exercise means it's done:
+// it passed, and its `I AM NOT DONE` marker was removed
+pub fn is_done(exercise: &Exercise, outcome: &Outcome) -> bool {
+    matches!(outcome, Outcome::Success(_)) && matches!(exercise.state(), Ok(State::Done))
+}
 
-    Ok(prompt_for_completion(exercise, Some(output.stdout)))
+// What the end user is told about the outcome of an exercise
+pub struct Summary {
+    // Whether or not the exercise passed
+    pub passed: bool,
+    // What happened, in a sentence
+    pub headline: String,
+    // The output worth showing along with it, which may be empty
+    pub details: String,
 }
 
-// Compile the given Exercise as a test harness and display
-// the output if verbose is set to true
-fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+// Sum up the outcome of the given Exercise for the end user. The output
+// of passing tests is only part of the details if `verbose` is set
+pub fn summarize(exercise: &Exercise, outcome: &Outcome, verbose: bool) -> Summary {
+    let failed = |headline: String, details: String| Summary {
+        passed: false,
+        headline,
+        details,
+    };
+    let passed = |headline: String, details: String| Summary {
+        passed: true,
+        headline,
+        details,
+    };
+    match outcome {
+        Outcome::CompileFailure(output) => failed(
+            format!("Compiling of {exercise} failed! Please try again. Here's the output:"),
+            output.stderr.clone(),
+        ),
+        Outcome::NotRejected => failed(
+            format!(
+                "{exercise} compiled, but it should be rejected with {}! Please try again.",
+                exercise.expected_error()
+            ),
+            String::new(),
+        ),
+        Outcome::WrongError(output, codes) => {
+            let found = match codes.is_empty() {
+                true => String::from("errors without a code"),
+                false => codes.join(", "),
+            };
+            failed(
+                format!(
+                    "{exercise} was rejected with {found} instead of {}! Here's the output:",
+                    exercise.expected_error()
+                ),
+                output.stderr.clone(),
+            )
+        }
+        Outcome::RunFailure(output) => match exercise.mode {
+            Mode::Test | Mode::Cargo | Mode::Exercism => failed(
+                format!("Testing of {exercise} failed! Please try again. Here's the output:"),
+                output.stdout.clone(),
+            ),
+            Mode::Compile | Mode::Clippy | Mode::CompileFail => {
+                failed(format!("Ran {exercise} with errors"), both(output))
+            }
+        },
+        Outcome::WrongOutput(output) => failed(
+            format!("Ran {exercise} but it didn't print the expected output!"),
+            wrong_output_details(exercise, output),
+        ),
+        Outcome::Timeout(output) => failed(
+            format!("Running {exercise} took too long and was stopped!"),
+            timeout_details(exercise, output),
+        ),
+        Outcome::LimitExceeded(output, violation) => failed(
+            format!("Running {exercise} was stopped by the sandbox!"),
+            violation_details(output, *violation),
+        ),
+        Outcome::Error(error) => failed(
+            format!("Failed to verify {exercise}: {error}"),
+            String::new(),
+        ),
+        Outcome::Success(output) => {
+            let stdout = || {
+                output
+                    .as_ref()
+                    .map(|o| o.stdout.clone())
+                    .unwrap_or_default()
+            };
+            match exercise.mode {
+                Mode::Test | Mode::Cargo | Mode::Exercism => passed(
+                    format!("Successfully tested {exercise}"),
+                    if verbose { stdout() } else { String::new() },
+                ),
+                Mode::Compile => passed(format!("Successfully ran {exercise}!"), stdout()),
+                Mode::Clippy => passed(format!("Successfully compiled {exercise}!"), String::new()),
+                Mode::CompileFail => passed(
+                    format!(
+                        "{exercise} was rejected with {}, as expected!",
+                        exercise.expected_error()
+                    ),
+                    output
+                        .as_ref()
+                        .map(|o| o.stderr.clone())
+                        .unwrap_or_default(),
+                ),
+            }
+        }
+    }
+}
 
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+// Everything a binary printed, stdout first
+fn both(output: &ExerciseOutput) -> String {
+    format!("{}\n{}", output.stdout, output.stderr)
+}
 
-    match result {
-        Ok(output) => {
-            if verbose {
-                println!("{}", output.stdout);
-            }
-            success!("Successfully tested {}", &exercise);
-            if let RunMode::Interactive = run_mode {
-                Ok(prompt_for_completion(exercise, None))
-            } else {
-                Ok(true)
+// Report the outcome of the given Exercise to the end user.
+// Returns whether or not the exercise is done
+fn report(exercise: &Exercise, outcome: &Outcome, run_mode: RunMode, verbose: bool) -> bool {
+    let Summary {
+        passed,
+        headline,
+        details,
+    } = summarize(exercise, outcome, verbose);
+    if !passed {
+        warn!("{}", headline);
+        if !details.is_empty() {
+            println!("{details}");
+        }
+        return false;
+    }
+
+    // The output of tests comes first, while that of binaries is shown
+    // along with the prompt
+    let prompt_output = match exercise.mode {
+        Mode::Test | Mode::Cargo | Mode::Exercism => {
+            if !details.is_empty() {
+                println!("{details}");
             }
+            success!("{}", headline);
+            None
         }
-        Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stdout);
-            Err(())
+        Mode::Compile | Mode::Clippy | Mode::CompileFail => {
+            success!("{}", headline);
+            Some(details).filter(|details| !details.is_empty())
         }
+    };
+
+    match run_mode {
+        RunMode::Interactive => prompt_for_completion(exercise, prompt_output),
+        RunMode::NonInteractive => true,
     }
 }
 
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a>(
-    exercise: &'a Exercise,
-    progress_bar: &ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
-
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
-            warn!(
-                "Compiling of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stderr);
-            Err(())
+// Tell the end user why the given Exercise failed
+pub fn report_failure(exercise: &Exercise, outcome: &Outcome) {
+    report(exercise, outcome, RunMode::NonInteractive, false);
+}
+
+// Show how the output of the given Exercise differs from what it's
+// expected to print
+fn wrong_output_details(exercise: &Exercise, output: &ExerciseOutput) -> String {
+    match &exercise.expected_stdout {
+        Some(ExpectedOutput::Exact(expected)) => {
+            // Trailing newlines don't matter, so they shouldn't show up as changes
+            let normalize = |text: &str| format!("{}\n", text.trim_end_matches('\n'));
+            format!(
+                "{} expected, {} actual:\n{}",
+                style("- lines").red(),
+                style("+ lines").green(),
+                diff::diff_lines(&normalize(expected), &normalize(&output.stdout))
+            )
         }
+        Some(ExpectedOutput::Pattern { regex }) => format!(
+            "Expected output matching {}, but got:\n{}",
+            style(regex).bold(),
+            output.stdout
+        ),
+        None => output.stdout.clone(),
     }
 }
 
+// What a binary killed for running too long printed, and why it was killed
+fn timeout_details(exercise: &Exercise, output: &ExerciseOutput) -> String {
+    format!(
+        "{}\nIt was still running after {} seconds. Is there an infinite loop?",
+        both(output),
+        exercise.timeout().as_secs()
+    )
+}
+
+// What a binary stopped by the sandbox printed, and why it was stopped
+fn violation_details(output: &ExerciseOutput, violation: Violation) -> String {
+    format!("{}\nThe exercise {violation}.", both(output))
+}
+
 fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) -> bool {
     let context = match exercise.state() {
-        State::Done => return true,
-        State::Pending(context) => context,
+        Ok(State::Done) => return true,
+        Ok(State::Pending(context)) => context,
+        Err(error) => {
+            warn!("{}", error);
+            return false;
+        }
     };
 
     let no_emoji = env::var("NO_EMOJI").is_ok();
@@ -149,8 +420,9 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) ->
 
     let success_msg = match exercise.mode {
         Mode::Compile => "The code is compiling!",
-        Mode::Test => "The code is compiling, and the tests pass!",
+        Mode::Test | Mode::Cargo | Mode::Exercism => "The code is compiling, and the tests pass!",
         Mode::Clippy => clippy_success_msg,
+        Mode::CompileFail => "The code is rejected with the expected error!",
     };
 
     println!();
@@ -196,3 +468,36 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) ->
 fn separator() -> console::StyledObject<&'static str> {
     style("====================").bold()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_summaries() {
+        let exercise = Exercise {
+            name: "tested".into(),
+            path: "tested.rs".into(),
+            mode: Mode::Test,
+            ..Default::default()
+        };
+        let output = || ExerciseOutput {
+            stdout: "test output".into(),
+            ..Default::default()
+        };
+
+        let passed = Outcome::Success(Some(output()));
+        let summary = summarize(&exercise, &passed, false);
+        assert!(summary.passed);
+        assert_eq!(summary.headline, "Successfully tested tested.rs");
+        assert!(summary.details.is_empty());
+        // The output of passing tests is only shown when asked for
+        assert_eq!(summarize(&exercise, &passed, true).details, "test output");
+
+        let failed = Outcome::RunFailure(output());
+        let summary = summarize(&exercise, &failed, false);
+        assert!(!summary.passed);
+        assert!(summary.headline.starts_with("Testing of tested.rs failed!"));
+        assert_eq!(summary.details, "test output");
+    }
+}
@@ -0,0 +1,266 @@
+use crate::run::run;
+use crate::shell::{self, Command as ShellCommand};
+use crate::verify::{verify, VerifyOptions};
+use notify::DebouncedEvent;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use rustlings::exercise::{Exercise, Mode, Topic};
+use rustlings::progress::{self, Progress};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub enum WatchStatus {
+    Finished,
+    Unfinished,
+}
+
+// The exercise file that was edited, if the event is about one
+pub fn edited_file(event: DebouncedEvent) -> Option<PathBuf> {
+    match event {
+        DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b)
+            if (b.extension() == Some(OsStr::new("rs"))
+                || b.file_name() == Some(OsStr::new("Cargo.toml")))
+                && b.exists() =>
+        {
+            b.as_path().canonicalize().ok()
+        }
+        _ => None,
+    }
+}
+
+// Watch the exercises directory, along with the Exercism crates outside of it
+pub fn watch_exercises(
+    watcher: &mut RecommendedWatcher,
+    exercises: &[Exercise],
+) -> notify::Result<()> {
+    watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+    for exercise in exercises {
+        if let Mode::Exercism = exercise.mode {
+            watcher.watch(&exercise.path, RecursiveMode::Recursive)?;
+        }
+    }
+    Ok(())
+}
+
+// The exercises that aren't done yet, leaving out the skipped ones.
+// Locked exercises come last, as they're only unlocked by the others
+pub fn pending<'a>(
+    exercises: &'a [Exercise],
+    progress: &Progress,
+    skipped: &HashSet<&str>,
+) -> Vec<&'a Exercise> {
+    let done = progress.done(exercises);
+    let (unlocked, locked): (Vec<&Exercise>, Vec<&Exercise>) = exercises
+        .iter()
+        .filter(|e| !done.contains(e.name.as_str()) && !skipped.contains(e.name.as_str()))
+        .partition(|e| progress::is_unlocked(e, exercises, &done));
+    unlocked.into_iter().chain(locked).collect()
+}
+
+// The exercises to verify once a file was edited: the exercise it
+// belongs to and the ones after it, then those that aren't done yet.
+// Editing a skipped exercise brings it back.
+pub fn pending_after_edit<'a>(
+    exercises: &'a [Exercise],
+    progress: &Progress,
+    filepath: &Path,
+    skipped: &mut HashSet<&str>,
+) -> Vec<&'a Exercise> {
+    skipped.retain(|name| {
+        !exercises
+            .iter()
+            .any(|e| e.name == *name && e.owns(filepath))
+    });
+    // Exercises that were verified before this event are left alone
+    exercises
+        .iter()
+        .skip_while(|e| !e.owns(filepath))
+        // .filter(|e| filepath.ends_with(&e.path))
+        .filter(|e| !skipped.contains(e.name.as_str()))
+        .chain(
+            pending(exercises, progress, skipped)
+                .into_iter()
+                .filter(|e| !e.owns(filepath)),
+        )
+        .collect()
+}
+
+// The exercises to verify when going to the given one: that exercise
+// first, then those that aren't done yet
+pub fn pending_from<'a>(
+    exercises: &'a [Exercise],
+    progress: &Progress,
+    target: &'a Exercise,
+    skipped: &mut HashSet<&str>,
+) -> Vec<&'a Exercise> {
+    skipped.remove(target.name.as_str());
+    let mut pending_exercises = vec![target];
+    pending_exercises.extend(
+        pending(exercises, progress, skipped)
+            .into_iter()
+            .filter(|e| e.name != target.name),
+    );
+    pending_exercises
+}
+
+// What to tell the end user once everything but skipped exercises is done
+pub fn skipped_message(exercises: &[Exercise], skipped: &HashSet<&str>) -> String {
+    let names: Vec<&str> = exercises
+        .iter()
+        .filter(|e| skipped.contains(e.name.as_str()))
+        .map(|e| e.name.as_str())
+        .collect();
+    format!(
+        "All the other exercises are done!\nYou skipped {}.\nType 'goto <name>' to get back to one of them.",
+        names.join(", ")
+    )
+}
+
+pub fn watch(
+    exercises: &[Exercise],
+    topics: &[Topic],
+    progress: Progress,
+    mut options: VerifyOptions,
+) -> notify::Result<WatchStatus> {
+    /* Clears the terminal with an ANSI escape code.
+    Works in UNIX and newer Windows terminals. */
+    fn clear_screen() {
+        println!("\x1Bc");
+    }
+
+    let (tx, rx) = channel();
+
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(2))?;
+    watch_exercises(&mut watcher, exercises)?;
+
+    clear_screen();
+
+    // The shell reads and updates the progress too, e.g. to remember hints
+    let progress = Arc::new(Mutex::new(progress));
+    let failed_exercise = Arc::new(Mutex::new(None));
+    // The exercises the end user moved past without solving them
+    let mut skipped: HashSet<&str> = HashSet::new();
+    let watched = Watched {
+        exercises,
+        progress: &progress,
+        failed_exercise: &failed_exercise,
+    };
+
+    let pending_exercises = pending(exercises, &progress.lock().unwrap(), &skipped);
+    if watched.verify(pending_exercises, options, &skipped) {
+        return Ok(WatchStatus::Finished);
+    }
+    let (shell_tx, shell_rx) = channel();
+    let _terminal = shell::spawn(exercises, topics, &failed_exercise, &progress, shell_tx);
+    loop {
+        match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(event) => {
+                if let Some(filepath) = edited_file(event) {
+                    let pending_exercises = pending_after_edit(
+                        exercises,
+                        &progress.lock().unwrap(),
+                        &filepath,
+                        &mut skipped,
+                    );
+                    clear_screen();
+                    if watched.verify(pending_exercises, options, &skipped) {
+                        return Ok(WatchStatus::Finished);
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                // the timeout expired, just check the shell commands below then loop again
+            }
+            Err(e) => println!("watch error: {e:?}"),
+        }
+
+        for command in shell_rx.try_iter() {
+            let pending_exercises = match command {
+                ShellCommand::Next => {
+                    let Some(current) = watched.current() else {
+                        println!("There is no exercise to skip");
+                        continue;
+                    };
+                    skipped.insert(&current.name);
+                    pending(exercises, &progress.lock().unwrap(), &skipped)
+                }
+                ShellCommand::Goto(name) => pending_from(
+                    exercises,
+                    &progress.lock().unwrap(),
+                    watched.find(&name),
+                    &mut skipped,
+                ),
+                ShellCommand::Run(name) => {
+                    let _ = run(watched.find(&name), options);
+                    continue;
+                }
+                ShellCommand::Verbose(verbose) => {
+                    options.verbose = verbose;
+                    if verbose {
+                        println!("The output of passing tests will be shown");
+                    } else {
+                        println!("The output of passing tests will be hidden");
+                    }
+                    continue;
+                }
+                ShellCommand::Quit => return Ok(WatchStatus::Unfinished),
+                _ => continue,
+            };
+            clear_screen();
+            if watched.verify(pending_exercises, options, &skipped) {
+                return Ok(WatchStatus::Finished);
+            }
+        }
+    }
+}
+
+// What watch mode keeps track of, shared with its shell
+struct Watched<'a> {
+    exercises: &'a [Exercise],
+    progress: &'a Mutex<Progress>,
+    // The exercise the end user has to work on, if any
+    failed_exercise: &'a Mutex<Option<Exercise>>,
+}
+
+impl<'a> Watched<'a> {
+    // The exercise the end user is working on
+    fn current(&self) -> Option<&'a Exercise> {
+        let current = self.failed_exercise.lock().unwrap();
+        let name = &current.as_ref()?.name;
+        self.exercises.iter().find(|e| &e.name == name)
+    }
+
+    // The shell only sends names of existing exercises
+    fn find(&self, name: &str) -> &'a Exercise {
+        self.exercises.iter().find(|e| e.name == name).unwrap()
+    }
+
+    // Verify the pending exercises, and keep track of the one
+    // the end user has to work on next.
+    // Returns whether or not every exercise is done.
+    fn verify(
+        &self,
+        pending_exercises: Vec<&'a Exercise>,
+        options: VerifyOptions,
+        skipped: &HashSet<&str>,
+    ) -> bool {
+        let mut progress = self.progress.lock().unwrap();
+        let failure = verify(pending_exercises, &mut progress, options);
+        let mut failed_exercise = self.failed_exercise.lock().unwrap();
+        match failure {
+            Ok(_) if skipped.is_empty() => true,
+            Ok(_) => {
+                *failed_exercise = None;
+                println!("{}", skipped_message(self.exercises, skipped));
+                false
+            }
+            Err((exercise, _)) => {
+                *failed_exercise = Some(exercise.clone());
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub fn answer() -> u32 {
+    41
+}
+
+#[test]
+fn answers() {
+    assert_eq!(answer(), 42, "THE ANSWER IS WRONG");
+}
@@ -0,0 +1,3 @@
+// I AM NOT DONE
+
+pub const NAME: &str = "asparagus";
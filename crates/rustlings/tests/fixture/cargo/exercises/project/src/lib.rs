@@ -0,0 +1,10 @@
+mod garden;
+
+pub fn welcome() -> String {
+    greeter::greet(garden::vegetables::NAME)
+}
+
+#[test]
+fn welcomes_the_vegetables() {
+    assert_eq!(welcome(), "Hello, asparagus!");
+}
@@ -0,0 +1,6 @@
+fn main() {
+    let sum = 0.1 + 0.2;
+    if sum == 0.3 {
+        println!("equal");
+    }
+}
@@ -0,0 +1,5 @@
+fn main() {
+    // TryFrom is only in the prelude as of the 2021 edition
+    let small = u8::try_from(300_u64).unwrap_or(u8::MAX);
+    println!("{small}");
+}
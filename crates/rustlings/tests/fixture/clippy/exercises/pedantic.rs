@@ -0,0 +1,5 @@
+fn main() {
+    let big: u64 = 300;
+    let small = big as u8;
+    println!("{small}");
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let greeting = String::from("Hello");
+    println!("{greeting}");
+}
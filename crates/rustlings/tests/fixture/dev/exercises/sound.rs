@@ -0,0 +1,10 @@
+// I AM NOT DONE
+
+fn double(x: i32) -> i32 {
+    x
+}
+
+#[test]
+fn doubles() {
+    assert_eq!(double(2), 4);
+}
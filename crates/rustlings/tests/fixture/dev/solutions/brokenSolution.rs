@@ -0,0 +1,8 @@
+fn double(x: i32) -> i32 {
+    x + 1
+}
+
+#[test]
+fn doubles() {
+    assert_eq!(double(2), 4);
+}
@@ -0,0 +1,9 @@
+fn main() {
+    let unused = 0;
+    let a: i32 = "one";
+    let b: i32 = "two";
+    let c: i32 = "three";
+    let d: i32 = "four";
+    let e: i32 = "five";
+    println!("{a} {b} {c} {d} {e}");
+}
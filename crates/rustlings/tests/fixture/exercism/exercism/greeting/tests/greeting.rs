@@ -0,0 +1,10 @@
+#[test]
+fn greets_by_name() {
+    assert!(greeting::greet("World").contains("World"));
+}
+
+#[test]
+#[ignore]
+fn greets_with_enthusiasm() {
+    assert_eq!(greeting::greet("World"), "Hello, World!");
+}
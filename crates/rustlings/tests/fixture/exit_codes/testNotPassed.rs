@@ -0,0 +1,4 @@
+#[test]
+fn not_passing() {
+    assert!(false);
+}
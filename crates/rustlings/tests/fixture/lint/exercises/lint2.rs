@@ -0,0 +1,5 @@
+// I AM NOT DONE
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
@@ -0,0 +1,9 @@
+use std::io::stdin;
+
+fn main() {
+    let mut name = String::new();
+    stdin().read_line(&mut name).unwrap();
+    println!("Hello, {}!", name.trim());
+    println!("You passed {} arguments.", std::env::args().count() - 1);
+    println!("Your language is {}.", std::env::var("LANGUAGE").unwrap());
+}
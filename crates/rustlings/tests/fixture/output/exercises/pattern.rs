@@ -0,0 +1,3 @@
+fn main() {
+    println!("{} + {} = {}", 2, 3, 2 + 3);
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let hog = vec![1u8; 8 << 30];
+    println!("{}", hog.iter().map(|&b| b as u64).sum::<u64>());
+}
@@ -0,0 +1,6 @@
+#[test]
+fn never_ends() {
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
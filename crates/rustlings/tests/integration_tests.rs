@@ -1,3 +1,4 @@
+use assert_cmd::cargo_bin;
 use assert_cmd::prelude::*;
 use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
@@ -7,43 +8,110 @@ use std::process::Command;
 
 #[test]
 fn runs_without_arguments() {
-    let mut cmd = Command::cargo_bin("rustlings").unwrap();
+    let mut cmd = Command::new(cargo_bin!("rustlings"));
     cmd.assert().success();
 }
 
 #[test]
 fn fails_when_in_wrong_dir() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .current_dir("tests/")
         .assert()
+        .code(5);
+}
+
+#[test]
+fn json_errors_go_to_stderr() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "list"])
+        .current_dir("tests/")
+        .assert()
+        .code(5)
+        .stdout("")
+        .stderr(predicates::str::contains(
+            "must be run from the rustlings directory",
+        ));
+}
+
+#[test]
+fn failing_tests_exit_with_1() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "testNotPassed"])
+        .current_dir("tests/fixture/exit_codes/")
+        .assert()
         .code(1);
 }
 
+#[test]
+fn unknown_options_are_usage_errors() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "testNotPassed", "--jobs", "2"])
+        .current_dir("tests/fixture/exit_codes/")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("Run rustlings --help"));
+}
+
 #[test]
 fn verify_all_success() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    let scratch = scratch_copy_of_fixture("success", "verify_all_success");
+    Command::new(cargo_bin!("rustlings"))
         .arg("verify")
-        .current_dir("tests/fixture/success")
+        .current_dir(&scratch)
         .assert()
         .success();
+    std::fs::remove_dir_all(scratch).unwrap();
 }
 
 #[test]
 fn verify_fails_if_some_fails() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .arg("verify")
         .current_dir("tests/fixture/failure")
         .assert()
-        .code(1);
+        .code(2);
+}
+
+#[test]
+fn verify_all_success_with_jobs() {
+    let scratch = scratch_copy_of_fixture("success", "verify_with_jobs");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["verify", "--jobs", "2"])
+        .current_dir(&scratch)
+        .assert()
+        .success();
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn verify_stops_at_first_failure_with_jobs() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["verify", "--jobs", "2"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(2)
+        .stdout(
+            predicates::str::contains("compFailure.rs failed")
+                .and(predicates::str::contains("testFailure.rs failed").not()),
+        );
+}
+
+#[test]
+fn verify_keep_going_reports_every_failure() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["verify", "--jobs", "2", "--keep-going"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(2)
+        .stdout(
+            predicates::str::contains("compFailure.rs failed")
+                .and(predicates::str::contains("testFailure.rs failed")),
+        );
 }
 
 #[test]
 fn run_single_compile_success() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "compSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
@@ -52,18 +120,48 @@ fn run_single_compile_success() {
 
 #[test]
 fn run_single_compile_failure() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "compFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
-        .code(1);
+        .code(2);
+}
+
+#[test]
+fn compile_failures_show_the_first_errors_only() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "cascade"])
+        .current_dir("tests/fixture/diagnostics/")
+        .assert()
+        .code(2)
+        .stdout(
+            predicates::str::contains("3 |     let a: i32 = \"one\";")
+                .and(predicates::str::contains("let c: i32"))
+                .and(predicates::str::contains("let d: i32").not())
+                .and(predicates::str::contains("... and 2 more errors."))
+                .and(predicates::str::contains("aborting due to").not()),
+        );
+}
+
+#[test]
+fn json_reports_every_diagnostic() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "run", "cascade"])
+        .current_dir("tests/fixture/diagnostics/")
+        .assert()
+        .code(2)
+        .stdout(
+            predicates::str::contains(r#""diagnostics":[{"message":"mismatched types""#)
+                .and(predicates::str::contains(
+                    r#""text":"    let e: i32 = \"five\";""#,
+                ))
+                .and(predicates::str::contains("aborting due to").not()),
+        );
 }
 
 #[test]
 fn run_single_test_success() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
@@ -72,48 +170,43 @@ fn run_single_test_success() {
 
 #[test]
 fn run_single_test_failure() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "testFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
-        .code(1);
+        .code(2);
 }
 
 #[test]
 fn run_single_test_not_passed() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "testNotPassed.rs"])
         .current_dir("tests/fixture/failure/")
         .assert()
-        .code(1);
+        .code(4);
 }
 
 #[test]
 fn run_single_test_no_filename() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .arg("run")
         .current_dir("tests/fixture/")
         .assert()
-        .code(1);
+        .code(4);
 }
 
 #[test]
 fn run_single_test_no_exercise() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "compNoExercise.rs"])
         .current_dir("tests/fixture/failure")
         .assert()
-        .code(1);
+        .code(4);
 }
 
 #[test]
 fn get_hint_for_single_test() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["hint", "testFailure"])
         .current_dir("tests/fixture/failure")
         .assert()
@@ -121,6 +214,36 @@ fn get_hint_for_single_test() {
         .stdout("Hello!\n");
 }
 
+#[test]
+fn hints_are_revealed_one_level_at_a_time() {
+    let scratch = scratch_copy_of_fixture("hints", "hints");
+    let hint = |args: &[&str]| {
+        Command::new(cargo_bin!("rustlings"))
+            .args(args)
+            .current_dir(&scratch)
+            .assert()
+            .code(0)
+    };
+
+    hint(&["hint", "hinted"]).stdout(
+        predicates::str::contains("Hint 1/3: Nudge")
+            .and(predicates::str::contains("Detail").not())
+            .and(predicates::str::contains("again")),
+    );
+    hint(&["hint", "hinted"]).stdout(
+        predicates::str::contains("Hint 1/3: Nudge")
+            .and(predicates::str::contains("Hint 2/3: Detail"))
+            .and(predicates::str::contains("Almost").not()),
+    );
+    hint(&["hint", "hinted", "--all"]).stdout(
+        predicates::str::contains("Hint 3/3: Almost the answer")
+            .and(predicates::str::contains("again").not()),
+    );
+    // Once everything has been revealed, it stays revealed
+    hint(&["hint", "hinted"]).stdout(predicates::str::contains("Hint 3/3"));
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
 #[test]
 fn all_exercises_require_confirmation() {
     for exercise in glob("exercises/**/*.rs").unwrap() {
@@ -140,8 +263,7 @@ fn all_exercises_require_confirmation() {
 
 #[test]
 fn run_compile_exercise_does_not_prompt() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "pending_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
@@ -151,8 +273,7 @@ fn run_compile_exercise_does_not_prompt() {
 
 #[test]
 fn run_test_exercise_does_not_prompt() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "pending_test_exercise"])
         .current_dir("tests/fixture/state")
         .assert()
@@ -162,8 +283,7 @@ fn run_test_exercise_does_not_prompt() {
 
 #[test]
 fn run_single_test_success_with_output() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["--nocapture", "run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
@@ -173,8 +293,7 @@ fn run_single_test_success_with_output() {
 
 #[test]
 fn run_single_test_success_without_output() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["run", "testSuccess"])
         .current_dir("tests/fixture/success/")
         .assert()
@@ -184,8 +303,7 @@ fn run_single_test_success_without_output() {
 
 #[test]
 fn run_rustlings_list() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
@@ -194,8 +312,7 @@ fn run_rustlings_list() {
 
 #[test]
 fn run_rustlings_list_no_pending() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["list"])
         .current_dir("tests/fixture/success")
         .assert()
@@ -205,8 +322,7 @@ fn run_rustlings_list_no_pending() {
 
 #[test]
 fn run_rustlings_list_both_done_and_pending() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["list"])
         .current_dir("tests/fixture/state")
         .assert()
@@ -216,8 +332,7 @@ fn run_rustlings_list_both_done_and_pending() {
 
 #[test]
 fn run_rustlings_list_without_pending() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["list", "--solved"])
         .current_dir("tests/fixture/state")
         .assert()
@@ -225,13 +340,835 @@ fn run_rustlings_list_without_pending() {
         .stdout(predicates::str::contains("Pending").not());
 }
 
+#[test]
+fn list_shows_exercises_that_did_not_compile() {
+    let scratch = scratch_copy_of_fixture("failure", "list_broken");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["verify"])
+        .current_dir(&scratch)
+        .assert()
+        .code(2);
+    // The failed build is cached, and the list is told about it
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::is_match(r"compFailure\s+compFailure.rs\s+Broken")
+                .unwrap()
+                .and(predicates::str::is_match(r"testFailure\s+testFailure.rs\s+Pending").unwrap()),
+        );
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "list"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains(r#""name":"compFailure","path":"compFailure.rs","mode":"compile","state":"pending","compiles":false"#)
+                .and(predicates::str::contains(r#""name":"testFailure","path":"testFailure.rs","mode":"test","state":"pending"}"#)),
+        );
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
 #[test]
 fn run_rustlings_list_without_done() {
-    Command::cargo_bin("rustlings")
-        .unwrap()
+    Command::new(cargo_bin!("rustlings"))
         .args(["list", "--unsolved"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn run_rustlings_list_requires_verification() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list", "--unsolved", "--names"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("unverified_exercise")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_json() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "list"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains(r#"{"name":"finished_exercise","path":"finished_exercise.rs","mode":"compile","state":"done"}"#)
+                .and(predicates::str::contains("Progress:").not()),
+        );
+}
+
+#[test]
+fn verify_json_reports_every_outcome() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "verify", "--keep-going"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(2)
+        .stdout(
+            predicates::str::contains(r#""name":"compFailure""#)
+                .and(predicates::str::contains(r#""status":"compile_failure""#))
+                .and(predicates::str::contains(r#""name":"testFailure""#))
+                .and(predicates::str::contains(r"\u001b").not()),
+        );
+}
+
+#[test]
+fn run_single_test_success_json() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "run", "testSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(0)
+        .stdout(
+            predicates::str::contains(r#""status":"success""#)
+                .and(predicates::str::contains("THIS TEST TOO SHALL PASS")),
+        );
+}
+
+#[test]
+fn run_with_expected_output() {
+    for exercise in ["greeting", "pattern"] {
+        Command::new(cargo_bin!("rustlings"))
+            .args(["run", exercise])
+            .current_dir("tests/fixture/output/")
+            .assert()
+            .code(0);
+    }
+}
+
+#[test]
+fn run_with_wrong_output_shows_diff() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "wrongGreeting"])
+        .current_dir("tests/fixture/output/")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("didn't print the expected output")
+                .and(predicates::str::contains("-Hello, World!"))
+                .and(predicates::str::contains("+Hello, world!")),
+        );
+}
+
+#[test]
+fn run_wrong_output_json() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "run", "wrongGreeting"])
+        .current_dir("tests/fixture/output/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(r#""status":"wrong_output""#));
+}
+
+#[test]
+fn run_compile_fail_rejected_with_expected_error() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "rejected"])
+        .current_dir("tests/fixture/compile_fail/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains(
+            "was rejected with E0382, as expected",
+        ));
+}
+
+#[test]
+fn run_compile_fail_accepted() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "accepted"])
+        .current_dir("tests/fixture/compile_fail/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "compiled, but it should be rejected with E0382",
+        ));
+}
+
+#[test]
+fn run_compile_fail_with_wrong_error() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "wrongError"])
+        .current_dir("tests/fixture/compile_fail/")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("was rejected with E0308 instead of E0382")
+                .and(predicates::str::contains("mismatched types")),
+        );
+}
+
+#[test]
+fn run_cargo_project_with_vendored_dependency() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "project"])
+        .current_dir("tests/fixture/cargo/")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains(
+            "Successfully tested exercises/project",
+        ));
+}
+
+#[test]
+fn run_cargo_project_failing_tests() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "brokenProject"])
+        .current_dir("tests/fixture/cargo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("THE ANSWER IS WRONG"));
+}
+
+#[test]
+fn run_exercism_crate_with_ignored_tests() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "greeting"])
+        .current_dir("tests/fixture/exercism/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("greets_with_enthusiasm"));
+}
+
+#[test]
+fn list_shows_exercism_crates() {
+    Command::new(cargo_bin!("rustlings"))
+        .arg("list")
+        .current_dir("tests/fixture/exercism/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "greeting         \texercism/greeting",
+        ))
+        .stdout(predicates::str::contains(
+            "exercism                \texercism",
+        ));
+}
+
+#[test]
+fn hint_of_exercism_crate_is_its_readme() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["hint", "greeting"])
+        .current_dir("tests/fixture/exercism/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("GREETING"))
+        .stdout(predicates::str::contains("Greet someone by their"));
+}
+
+#[test]
+fn run_clippy_with_default_lints() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "tidy"])
+        .current_dir("tests/fixture/clippy/")
+        .assert()
+        .code(0);
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "floatCmp"])
+        .current_dir("tests/fixture/clippy/")
+        .assert()
+        .code(2)
+        .stdout(predicates::str::contains("float_cmp"));
+}
+
+#[test]
+fn run_clippy_with_exercise_lints() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "pedantic"])
+        .current_dir("tests/fixture/clippy/")
+        .assert()
+        .code(2)
+        .stdout(predicates::str::contains("cast_possible_truncation"));
+}
+
+#[test]
+fn run_clippy_with_exercise_edition() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "modernEdition"])
+        .current_dir("tests/fixture/clippy/")
+        .assert()
+        .code(0);
+}
+
+#[test]
+fn verify_clippy_exercises_with_jobs() {
+    // Every clippy exercise is checked in its own project, so concurrent
+    // checks must not report the lints of one another
+    let scratch = scratch_copy_of_fixture("clippy", "clippy_with_jobs");
+    let output = Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "verify", "--jobs", "4", "--keep-going"])
+        .current_dir(&scratch)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let record = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(&format!(r#""name":"{name}""#)))
+            .unwrap()
+            .to_string()
+    };
+    assert!(record("tidy").contains(r#""status":"success""#));
+    assert!(record("modernEdition").contains(r#""status":"success""#));
+    let float_cmp = record("floatCmp");
+    assert!(float_cmp.contains("float_cmp") && !float_cmp.contains("cast_possible_truncation"));
+    let pedantic = record("pedantic");
+    assert!(pedantic.contains("cast_possible_truncation") && !pedantic.contains("float_cmp"));
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn run_single_compile_timeout() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "infiniteLoop"])
+        .current_dir("tests/fixture/timeout/")
+        .assert()
+        .code(3)
+        .stdout(
+            predicates::str::contains("took too long")
+                .and(predicates::str::contains("Looping forever")),
+        );
+}
+
+#[test]
+fn verify_test_timeout_json() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "verify", "--keep-going"])
+        .current_dir("tests/fixture/timeout/")
+        .assert()
+        .code(3)
+        .stdout(
+            predicates::str::contains(r#""name":"infiniteTest""#)
+                .and(predicates::str::contains(r#""status":"timeout""#)),
+        );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn run_sandboxed_reports_memory_limit() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--sandbox", "run", "memoryHog"])
+        .current_dir("tests/fixture/sandbox/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("ran out of memory"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn run_sandboxed_abort_is_not_a_memory_limit() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--sandbox", "run", "aborter"])
+        .current_dir("tests/fixture/sandbox/")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("aborter.rs")
+                .and(predicates::str::contains("ran out of memory").not()),
+        );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn verify_sandboxed_success() {
+    let scratch = scratch_copy_of_fixture("success", "verify_sandboxed");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--sandbox", "verify"])
+        .current_dir(&scratch)
+        .assert()
+        .success();
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+// Copy a fixture to a scratch directory, so that the progress store
+// committed along with it isn't rewritten. Build outputs aren't copied
+fn scratch_copy_of_fixture(fixture: &str, name: &str) -> std::path::PathBuf {
+    fn copy_dir(from: &std::path::Path, to: &std::path::Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            if entry.file_name() == "target" {
+                continue;
+            }
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &to.join(entry.file_name()));
+            } else {
+                std::fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+            }
+        }
+    }
+    let scratch = std::env::temp_dir().join(format!("rustlings_{}_{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&scratch);
+    copy_dir(
+        &std::path::Path::new("tests/fixture").join(fixture),
+        &scratch,
+    );
+    scratch
+}
+
+// Copy info.toml and the exercises to a scratch directory,
+// so that tests can freely modify them
+fn scratch_copy_of_exercises(name: &str) -> std::path::PathBuf {
+    let scratch = std::env::temp_dir().join(format!("rustlings_{}_{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&scratch);
+    std::fs::create_dir_all(&scratch).unwrap();
+    std::fs::copy("info.toml", scratch.join("info.toml")).unwrap();
+    for exercise in glob("exercises/**/*.rs").unwrap() {
+        let path = exercise.unwrap();
+        std::fs::create_dir_all(scratch.join(path.parent().unwrap())).unwrap();
+        std::fs::copy(&path, scratch.join(&path)).unwrap();
+    }
+    scratch
+}
+
+#[test]
+fn reset_single_exercise() {
+    let scratch = scratch_copy_of_exercises("reset_single");
+    let path = scratch.join("exercises/intro/intro2.rs");
+    let original = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, "mangled").unwrap();
+
+    Command::new(cargo_bin!("rustlings"))
+        .args(["reset", "intro2"])
+        .current_dir(&scratch)
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn reset_topic_requires_confirmation() {
+    let scratch = scratch_copy_of_exercises("reset_topic");
+    let path = scratch.join("exercises/variables/variables1.rs");
+    let original = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, "mangled").unwrap();
+
+    assert_cmd::Command::new(cargo_bin!("rustlings"))
+        .args(["reset", "variables"])
+        .current_dir(&scratch)
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Nothing was reset"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "mangled");
+
+    assert_cmd::Command::new(cargo_bin!("rustlings"))
+        .args(["reset", "variables"])
+        .current_dir(&scratch)
+        .write_stdin("y\n")
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn reset_unknown_exercise() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["reset", "compSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(4);
+}
+
+#[test]
+fn diff_shows_changes_to_tests() {
+    let scratch = scratch_copy_of_exercises("diff");
+    let path = scratch.join("exercises/tests/tests1.rs");
+    let source = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, source.replace("assert!(true)", "assert!(1 == 1)")).unwrap();
+
+    Command::new(cargo_bin!("rustlings"))
+        .args(["diff", "tests1"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("-        assert!(true)")
+                .and(predicates::str::contains("+        assert!(1 == 1)"))
+                .and(predicates::str::contains("The tests of")),
+        );
+
+    Command::new(cargo_bin!("rustlings"))
+        .args(["diff", "tests2"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("hasn't been changed"));
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn solution_of_done_exercise() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["solution", "compSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("The solution to compSuccess"));
+}
+
+#[test]
+fn solution_compared_side_by_side() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["solution", "--compare", "compSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Your code").and(
+                predicates::str::contains("fn main() {")
+                    .and(predicates::str::contains("The solution to compSuccess")),
+            ),
+        );
+}
+
+#[test]
+fn solution_of_pending_exercise_is_hidden() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["solution", "pending_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("The solution to").not());
+}
+
+#[test]
+fn solution_of_pending_exercise_with_force() {
+    assert_cmd::Command::new(cargo_bin!("rustlings"))
+        .args(["solution", "--force", "pending_exercise"])
+        .current_dir("tests/fixture/state")
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "The solution to pending_exercise",
+        ));
+}
+
+#[test]
+fn solution_with_force_declined() {
+    assert_cmd::Command::new(cargo_bin!("rustlings"))
+        .args(["solution", "--force", "pending_exercise"])
+        .current_dir("tests/fixture/state")
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("The solution wasn't shown")
+                .and(predicates::str::contains("The solution to").not()),
+        );
+}
+
+#[test]
+fn solution_missing() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["solution", "testSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("There is no solution"));
+}
+
+#[test]
+fn dev_check_reports_problems() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["dev", "check"])
+        .env("NO_EMOJI", "1")
+        .current_dir("tests/fixture/dev")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("ok exercises/sound.rs")
+                .and(predicates::str::contains("x exercises/alreadySolved.rs"))
+                .and(predicates::str::contains("already passes"))
+                .and(predicates::str::contains("x exercises/brokenSolution.rs"))
+                .and(predicates::str::contains("the reference solution fails"))
+                .and(predicates::str::contains("x exercises/unsolvable.rs"))
+                .and(predicates::str::contains("there is no reference solution"))
+                .and(predicates::str::contains("3 of 4 exercises have problems")),
+        );
+}
+
+#[test]
+fn list_shows_progress_by_topic() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list"])
+        .current_dir("tests/fixture/topics/")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::is_match(r"basics\s+exercises/basics\s+1/2 \(50.00 %\)")
+                .unwrap()
+                .and(predicates::str::is_match(r"quiz1\s+exercises\s+1/1 \(100.00 %\)").unwrap()),
+        );
+}
+
+#[test]
+fn list_only_the_exercises_of_a_topic() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list", "--topic", "basics", "--names"])
+        .current_dir("tests/fixture/topics/")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("basics1")
+                .and(predicates::str::contains("basics2"))
+                .and(predicates::str::contains("quiz1").not()),
+        );
+}
+
+#[test]
+fn list_unknown_topic() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list", "--topic", "advanced"])
+        .current_dir("tests/fixture/topics/")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("No topic found for 'advanced'!"));
+}
+
+#[test]
+fn verify_only_the_exercises_of_a_topic() {
+    let scratch = scratch_copy_of_fixture("topics", "verify_topic");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["verify", "--topic", "quiz1"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("basics").not());
+    Command::new(cargo_bin!("rustlings"))
+        .args(["verify", "--topic", "basics"])
+        .current_dir(&scratch)
+        .assert()
+        .code(2)
+        .stdout(
+            predicates::str::contains("basics2.rs failed")
+                .and(predicates::str::contains("quiz1").not()),
+        );
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn readme_renders_topic_readme() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["readme", "basics"])
+        .current_dir("tests/fixture/topics/")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("BASICS").and(predicates::str::contains(
+                "Hello, World! (https://doc.rust-lang.org/book/ch01-02-hello-world.html)",
+            )),
+        );
+}
+
+#[test]
+fn readme_of_topic_without_readme() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["readme", "quiz1"])
+        .current_dir("tests/fixture/topics/")
+        .assert()
+        .code(7)
+        .stdout(predicates::str::contains(
+            "Failed to read the README of quiz1",
+        ));
+}
+
+#[test]
+fn list_shows_locked_exercises_and_topics() {
+    // Other tests build the exercises of the fixture, which the list would
+    // tell about
+    let scratch = scratch_copy_of_fixture("prerequisites", "list_locked");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::is_match(r"intro1\s+exercises/intro/intro1.rs\s+Locked")
+                .unwrap()
+                .and(
+                    predicates::str::is_match(r"intro2\s+exercises/intro/intro2.rs\s+Pending")
+                        .unwrap(),
+                )
+                .and(
+                    predicates::str::is_match(r"after1\s+exercises/after/after1.rs\s+Locked")
+                        .unwrap(),
+                )
+                .and(predicates::str::contains("Locked, requires quiz1")),
+        );
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn run_next_skips_locked_exercises() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["run", "next"])
+        .current_dir("tests/fixture/prerequisites/")
+        .assert()
+        .code(2)
+        .stdout(predicates::str::contains("exercises/intro/intro2.rs"));
+}
+
+#[test]
+fn new_exercise_at_end_of_topic() {
+    let scratch = scratch_copy_of_exercises("new_in_topic");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["new", "intro", "intro3", "--mode", "test"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Created exercises/intro/intro3.rs",
+        ));
+    let source = std::fs::read_to_string(scratch.join("exercises/intro/intro3.rs")).unwrap();
+    assert!(source.contains("// I AM NOT DONE") && source.contains("#[cfg(test)]"));
+    // Its original is kept for the next build to embed
+    let original =
+        std::fs::read_to_string(scratch.join("pristine/exercises/intro/intro3.rs")).unwrap();
+    assert_eq!(original, source);
+
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list", "--names"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("intro2\nintro3\nvariables1\n"));
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn new_exercise_in_new_topic() {
+    let scratch = scratch_copy_of_exercises("new_topic");
+    Command::new(cargo_bin!("rustlings"))
+        .args(["new", "lifetimes", "lifetimes1"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Created exercises/lifetimes/README.md",
+        ));
+    assert!(scratch.join("exercises/lifetimes/lifetimes1.rs").exists());
+
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list", "--topic", "lifetimes", "--names"])
+        .current_dir(&scratch)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("lifetimes1"));
+    std::fs::remove_dir_all(scratch).unwrap();
+}
+
+#[test]
+fn new_exercise_with_taken_name() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["new", "intro", "intro1"])
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains(
+            "there already is an exercise named 'intro1'",
+        ));
+}
+
+#[test]
+fn new_exercise_outside_exercises() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["new", "../x", "y"])
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains(
+            "the name of the topic isn't an identifier: '../x'",
+        ));
+    assert!(!std::path::Path::new("../x").exists());
+}
+
+#[test]
+fn lint_info_reports_errors_and_warnings() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["lint-info"])
+        .current_dir("tests/fixture/lint/")
+        .assert()
+        .code(5)
+        .stdout(
+            predicates::str::contains(
+                "info.toml:17: error: there already is an exercise named 'lint1'",
+            )
+            .and(predicates::str::contains(
+                "info.toml:17: error: the file of lint1 doesn't exist: exercises/missing.rs",
+            ))
+            .and(predicates::str::contains(
+                "info.toml:2: warning: the README of topic lint doesn't exist: exercises/lint/README.md",
+            ))
+            .and(predicates::str::contains(
+                "info.toml:5: warning: lint1 has no hint",
+            ))
+            .and(predicates::str::contains(
+                "exercises/lint2.rs: warning: lint2 is a test exercise without any #[test]",
+            ))
+            .and(predicates::str::contains(
+                "exercises/orphan.rs: warning: no exercise in info.toml refers to this file",
+            ))
+            .and(predicates::str::contains("2 errors, 5 warnings")),
+        );
+}
+
+#[test]
+fn info_errors_are_reported_at_startup() {
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list"])
+        .current_dir("tests/fixture/lint/")
+        .assert()
+        .code(5)
+        .stdout(
+            predicates::str::contains("error: there already is an exercise named 'lint1'")
+                .and(predicates::str::contains("warning").not())
+                .and(predicates::str::contains("Please fix info.toml")),
+        );
+
+    // Stdout is kept for JSON records
+    Command::new(cargo_bin!("rustlings"))
+        .args(["--format", "json", "list"])
+        .current_dir("tests/fixture/lint/")
+        .assert()
+        .code(5)
+        .stdout(predicates::str::is_empty())
+        .stderr(
+            predicates::str::contains("error: there already is an exercise named 'lint1'")
+                .and(predicates::str::contains("Please fix info.toml")),
+        );
+}
+
+#[test]
+fn unknown_keys_in_info_are_reported() {
+    let scratch =
+        std::env::temp_dir().join(format!("rustlings_{}_unknown_key", std::process::id()));
+    let _ = std::fs::remove_dir_all(&scratch);
+    std::fs::create_dir_all(&scratch).unwrap();
+    std::fs::write(
+        scratch.join("info.toml"),
+        "[[exercises]]\nname = \"typo\"\npath = \"typo.rs\"\nmode = \"compile\"\nhnt = \"\"\n",
+    )
+    .unwrap();
+
+    Command::new(cargo_bin!("rustlings"))
+        .args(["list"])
+        .current_dir(&scratch)
+        .assert()
+        .code(5)
+        .stdout(
+            predicates::str::contains("line 5")
+                .and(predicates::str::contains("unknown field `hnt`")),
+        );
+    std::fs::remove_dir_all(scratch).unwrap();
+}